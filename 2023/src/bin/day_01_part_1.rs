@@ -17,7 +17,7 @@ fn solution(input: &str) -> u32 {
             // Get the first digit.
             let first = iter.next().unwrap();
             // Get the last digit defaulting to the first if no more digits.
-            let last = iter.last().unwrap_or(first);
+            let last = iter.next_back().unwrap_or(first);
 
             // Join the digis into the "full" number.
             first * 10 + last
@@ -23,9 +23,9 @@ fn parse_line(line: &[u8]) -> u32 {
         .flat_map(|r| (0..r).rev().map(move |l| &line[l..r]))
         .find_map(parse_digit);
 
-    if first.is_some() && last.is_some() {
+    if let (Some(first), Some(last)) = (first, last) {
         // Join the digis into a number.
-        first.unwrap() * 10 + last.unwrap()
+        first * 10 + last
     } else {
         0
     }
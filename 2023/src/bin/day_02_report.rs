@@ -0,0 +1,127 @@
+use std::{env, fmt::Write, process};
+
+use aoc_2023::day_02::{Cubes, Games};
+use itertools::Itertools;
+
+// Print per game statistics for day 2. Bags to check games against can be
+// passed as arguments in round format, eg.
+// `cargo run --bin day_02_report -- "12 red, 13 green, 14 blue" "20 red"`.
+// Defaults to the part 1 bag.
+fn main() {
+    let input = include_str!("../../input/day_02");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let bags: Vec<Cubes> = if args.is_empty() {
+        vec!["12 red, 13 green, 14 blue".parse().unwrap()]
+    } else {
+        args.iter()
+            .map(|arg| {
+                arg.parse().unwrap_or_else(|error| {
+                    eprintln!("invalid bag \"{arg}\": {error}");
+                    process::exit(1);
+                })
+            })
+            .collect()
+    };
+
+    let games: Games = input.parse().unwrap_or_else(|error| {
+        eprintln!("invalid input: {error}");
+        process::exit(1);
+    });
+
+    print!("{}", report(&games, &bags));
+}
+
+fn report(games: &Games, bags: &[Cubes]) -> String {
+    let mut output = String::new();
+
+    // Legend of the bags being checked.
+    for (index, bag) in bags.iter().enumerate() {
+        writeln!(output, "bag {}: {bag}", index + 1).unwrap();
+    }
+    writeln!(output).unwrap();
+
+    // One row per game with its minimum bag, power and the bags it's
+    // possible under.
+    writeln!(
+        output,
+        "{:>5} {:>5} {:>5} {:>5} {:>7}  possible",
+        "game", "red", "green", "blue", "power"
+    )
+    .unwrap();
+    games
+        .minimum_cubes()
+        .zip(games.possible_bags(bags))
+        .for_each(|((id, minimum), (_, possible))| {
+            writeln!(
+                output,
+                "{id:>5} {:>5} {:>5} {:>5} {:>7}  {}",
+                minimum.red,
+                minimum.green,
+                minimum.blue,
+                minimum.power(),
+                possible.iter().map(|index| index + 1).join(", "),
+            )
+            .unwrap();
+        });
+    writeln!(output).unwrap();
+
+    // Totals over all games.
+    let max_draws = games.max_draws();
+    writeln!(
+        output,
+        "max draws: {} red, {} green, {} blue",
+        max_draws.red, max_draws.green, max_draws.blue
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "total power: {}",
+        games.powers().map(|(_, power)| power).sum::<u32>()
+    )
+    .unwrap();
+    for (index, bag) in bags.iter().enumerate() {
+        let possible: Vec<u32> = games.possible_games(bag).map(|game| game.id).collect();
+        writeln!(
+            output,
+            "bag {}: {} possible games, id sum {}",
+            index + 1,
+            possible.len(),
+            possible.iter().sum::<u32>()
+        )
+        .unwrap();
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_report() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
+        let games: Games = input.parse().unwrap();
+        let bags: Vec<Cubes> = vec![
+            "12 red, 13 green, 14 blue".parse().unwrap(),
+            "20 red, 20 green, 20 blue".parse().unwrap(),
+        ];
+        let result = report(&games, &bags);
+        let expected = "bag 1: 12 red, 13 green, 14 blue
+bag 2: 20 red, 20 green, 20 blue
+
+ game   red green  blue   power  possible
+    1     4     2     6      48  1, 2
+    2     1     3     4      12  1, 2
+    3    20    13     6    1560  2
+
+max draws: 20 red, 13 green, 6 blue
+total power: 1620
+bag 1: 2 possible games, id sum 3
+bag 2: 3 possible games, id sum 6
+";
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,35 @@
+use std::{env, fs, process};
+
+use aoc_2023::day_03::{Format, Rules, Schematic};
+
+// Print the day 3 schematic annotated with which numbers were counted.
+//
+// `cargo run --bin day_03_render` prints with ANSI colours,
+// `cargo run --bin day_03_render -- --plain` prints with plain markers and
+// `cargo run --bin day_03_render -- --html schematic.html` writes an HTML file.
+fn main() {
+    let input = include_str!("../../input/day_03");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let schematic = Schematic::parse(input).unwrap_or_else(|error| {
+        eprintln!("invalid schematic: {error}");
+        process::exit(1);
+    });
+    let rules = Rules::default();
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        [] => print!("{}", schematic.render(&rules, Format::Ansi)),
+        ["--plain"] => print!("{}", schematic.render(&rules, Format::Plain)),
+        ["--html", path] => {
+            fs::write(path, schematic.render(&rules, Format::Html)).unwrap_or_else(|error| {
+                eprintln!("failed to write {path}: {error}");
+                process::exit(1);
+            })
+        }
+        _ => {
+            eprintln!("usage: day_03_render [--plain | --html <path>]");
+            process::exit(1);
+        }
+    }
+}
@@ -1,6 +1,4 @@
-use std::collections::HashSet;
-
-use itertools::Itertools;
+use aoc_2023::day_04::{parse_scratchcards, Scratchcard};
 
 fn main() {
     let input = include_str!("../../input/day_04");
@@ -9,32 +7,12 @@ fn main() {
 }
 
 fn solution(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            let mut iter = line.split(':');
-            iter.next();
-            iter.next()
-                .unwrap()
-                .split('|')
-                .map(|numbers| {
-                    numbers
-                        .split_ascii_whitespace()
-                        .map(|number| number.parse().unwrap())
-                        .collect::<HashSet<u32>>()
-                })
-                .tuple_windows()
-                .map(|(winning, mine)| winning.intersection(&mine).count() as u32)
-                .map(|winning_count| {
-                    if winning_count == 0 {
-                        0
-                    } else {
-                        2_u32.pow(winning_count - 1)
-                    }
-                })
-                .next()
-                .unwrap()
-        })
+    parse_scratchcards(input)
+        .unwrap()
+        .iter()
+        // Score each card.
+        .map(Scratchcard::points)
+        // Sum the points.
         .sum()
 }
 
@@ -1,6 +1,4 @@
-use std::collections::HashSet;
-
-use itertools::Itertools;
+use aoc_2023::day_04::{parse_scratchcards, simulate, OverflowPolicy};
 
 fn main() {
     let input = include_str!("../../input/day_04");
@@ -8,38 +6,16 @@ fn main() {
     println!("{result}");
 }
 
-fn solution(input: &str) -> u32 {
-    let mut card_counts = vec![1_u32; input.lines().count()];
-
-    input
-        .lines()
-        .map(|line| {
-            let mut iter = line.split(':');
-            iter.next();
-            iter.next()
-                .unwrap()
-                .split('|')
-                .map(|numbers| {
-                    numbers
-                        .split_ascii_whitespace()
-                        .map(|number| number.parse().unwrap())
-                        .collect::<HashSet<u32>>()
-                })
-                .tuple_windows()
-                .map(|(winning, mine)| winning.intersection(&mine).count())
-                .next()
-                .unwrap()
-        })
-        .enumerate()
-        .filter(|(_, match_count)| *match_count != 0)
-        .for_each(|(index, match_count)| {
-            let current_card_count = card_counts[index];
-            card_counts[(index + 1)..=(index + match_count)]
-                .iter_mut()
-                .for_each(|card_count| *card_count += current_card_count);
-        });
+fn solution(input: &str) -> u64 {
+    let scratchcards = parse_scratchcards(input).unwrap();
 
-    card_counts.iter().sum()
+    // The puzzle promises cards never win copies past the end of the table.
+    simulate(&scratchcards, OverflowPolicy::Error)
+        .unwrap()
+        .iter()
+        // Sum the copies of every card.
+        .map(|card| card.copies)
+        .sum()
 }
 
 #[cfg(test)]
@@ -58,7 +58,7 @@ fn solution(mut input: &str) -> u64 {
                 .0 as u64
         })
         // Calculate the LCM (lowest common multiple) of all indexes.
-        .reduce(|acc, index| lcm(acc, index))
+        .reduce(lcm)
         .unwrap()
 }
 
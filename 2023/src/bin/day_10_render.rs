@@ -0,0 +1,33 @@
+use std::{env, fs, process};
+
+use aoc_2023::day_10::{Format, Tiles};
+
+// Print the day 10 pipe map redrawn with box-drawing characters.
+//
+// `cargo run --bin day_10_render` prints with ANSI colours and
+// `cargo run --bin day_10_render -- --output pipes.txt` writes plain text to
+// a file.
+fn main() {
+    let input = include_str!("../../input/day_10");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let tiles = Tiles::parse(input).unwrap_or_else(|error| {
+        eprintln!("invalid pipe map: {error}");
+        process::exit(1);
+    });
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        [] => print!("{}", tiles.render(Format::Ansi)),
+        ["--output", path] => {
+            fs::write(path, tiles.render(Format::Plain)).unwrap_or_else(|error| {
+                eprintln!("failed to write {path}: {error}");
+                process::exit(1);
+            })
+        }
+        _ => {
+            eprintln!("usage: day_10_render [--output <path>]");
+            process::exit(1);
+        }
+    }
+}
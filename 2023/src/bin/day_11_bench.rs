@@ -0,0 +1,42 @@
+use std::time::{Duration, Instant};
+
+use aoc_2023::day_11::{distance_sum, distance_sum_pairwise, Galaxy};
+
+// Compare the pairwise and sorted distance sums on generated universes.
+//
+// Run with `cargo run --release --bin day_11_bench`.
+fn main() {
+    for galaxy_count in [10_000, 20_000, 40_000] {
+        let galaxies = generate(galaxy_count, galaxy_count as u64);
+
+        let (pairwise, pairwise_time) = time(|| distance_sum_pairwise(&galaxies));
+        let (sorted, sorted_time) = time(|| distance_sum(&galaxies));
+        assert_eq!(pairwise, sorted);
+
+        println!(
+            "{galaxy_count} galaxies: sum {sorted}, pairwise {pairwise_time:?}, sorted {sorted_time:?}"
+        );
+    }
+}
+
+// Scatter galaxies over a universe about a hundred times as large as the
+// puzzle input's, with coordinates from a xorshift generator.
+fn generate(galaxy_count: usize, seed: u64) -> Vec<Galaxy> {
+    let mut state = seed | 1;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as usize
+    };
+
+    (0..galaxy_count)
+        .map(|_| Galaxy(next() % 14_000, next() % 14_000))
+        .collect()
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
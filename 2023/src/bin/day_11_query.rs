@@ -0,0 +1,72 @@
+use std::{env, fs, process};
+
+use aoc_2023::day_11::{
+    distance_between, distance_matrix_csv, farthest_pair, nearest_galaxies, SpaceTime,
+};
+
+// Query the day 11 galaxies after expansion. Galaxies are numbered from 1 in
+// reading order, as in the puzzle text.
+//
+// `cargo run --bin day_11_query -- nearest` lists each galaxy's nearest
+// neighbour, `farthest` prints the pair furthest apart, `distance 5 9` prints
+// the distance between two galaxies and `matrix distances.csv` writes every
+// distance as CSV. Expansion defaults to part 1's factor of 2, and can be
+// changed with eg. `--factor 1000000` before the query.
+fn main() {
+    let input = include_str!("../../input/day_11");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let (factor, query) = match args.as_slice() {
+        ["--factor", factor, query @ ..] => (parse_number(factor), query),
+        query => (2, query),
+    };
+    let galaxies = SpaceTime::parse(input).expand(factor, factor);
+
+    match query {
+        ["nearest"] => {
+            for (index, nearest) in nearest_galaxies(&galaxies).iter().enumerate() {
+                match nearest {
+                    Some((other, distance)) => {
+                        println!("galaxy {}: galaxy {} at {distance}", index + 1, other + 1)
+                    }
+                    None => println!("galaxy {}: none", index + 1),
+                }
+            }
+        }
+        ["farthest"] => match farthest_pair(&galaxies) {
+            Some((first, second, distance)) => {
+                println!("galaxies {} and {}: {distance}", first + 1, second + 1)
+            }
+            None => println!("fewer than two galaxies"),
+        },
+        ["distance", first, second] => {
+            match distance_between(&galaxies, parse_number(first), parse_number(second)) {
+                Some(distance) => println!("{distance}"),
+                None => {
+                    eprintln!("galaxies are numbered 1 to {}", galaxies.len());
+                    process::exit(1);
+                }
+            }
+        }
+        ["matrix", path] => {
+            fs::write(path, distance_matrix_csv(&galaxies)).unwrap_or_else(|error| {
+                eprintln!("failed to write {path}: {error}");
+                process::exit(1);
+            })
+        }
+        _ => {
+            eprintln!(
+                "usage: day_11_query [--factor <n>] (nearest | farthest | distance <a> <b> | matrix <path>)"
+            );
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_number(arg: &str) -> usize {
+    arg.parse().unwrap_or_else(|error| {
+        eprintln!("invalid number \"{arg}\": {error}");
+        process::exit(1);
+    })
+}
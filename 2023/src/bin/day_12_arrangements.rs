@@ -0,0 +1,42 @@
+use std::{env, process};
+
+use aoc_2023::day_12::Record;
+use itertools::Itertools;
+use winnow::Parser;
+
+// Print every arrangement of a day 12 condition record, optionally unfolded,
+// eg. `cargo run --bin day_12_arrangements -- "?###???????? 3,2,1" 2`.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let (input, factor) = match args.as_slice() {
+        [record] => (*record, 1),
+        [record, factor] => (
+            *record,
+            factor.parse().unwrap_or_else(|error| {
+                eprintln!("invalid fold factor \"{factor}\": {error}");
+                process::exit(1);
+            }),
+        ),
+        _ => {
+            eprintln!("usage: day_12_arrangements <record> [fold factor]");
+            process::exit(1);
+        }
+    };
+
+    let record = Record::parse
+        .parse(input)
+        .map(|record| record.unfold(factor))
+        .unwrap_or_else(|error| {
+            eprintln!("invalid record: {error}");
+            process::exit(1);
+        });
+
+    let mut count = 0;
+    for arrangement in record.arrangements() {
+        println!("{}", arrangement.iter().join(""));
+        count += 1;
+    }
+    println!("{count} arrangements");
+}
@@ -0,0 +1,33 @@
+use aoc_2023::day_12::parse_records;
+
+fn main() {
+    let input = include_str!("../../input/day_12");
+    let result = solution(input);
+    println!("{result}");
+}
+
+fn solution(input: &str) -> u64 {
+    parse_records(input)
+        .unwrap()
+        .iter()
+        .map(|record| record.arrangement_count())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_solution() {
+        let input = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+        let result = solution(input);
+        let expected = 21;
+        assert_eq!(result, expected);
+    }
+}
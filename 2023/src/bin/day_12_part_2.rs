@@ -0,0 +1,36 @@
+use aoc_2023::day_12::parse_records;
+
+fn main() {
+    let input = include_str!("../../input/day_12");
+    let result = solution(input, 5);
+    println!("{result}");
+}
+
+fn solution(input: &str, factor: usize) -> u64 {
+    parse_records(input)
+        .unwrap()
+        .iter()
+        .map(|record| record.unfold(factor).arrangement_count())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::*;
+
+    #[rstest]
+    #[case(1, 21)]
+    #[case(5, 525152)]
+    fn test_solution(#[case] factor: usize, #[case] expected: u64) {
+        let input = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+        let result = solution(input, factor);
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,42 @@
+use aoc_2023::day_13::parse_patterns;
+
+fn main() {
+    let input = include_str!("../../input/day_13");
+    let result = solution(input);
+    println!("{result}");
+}
+
+fn solution(input: &str) -> usize {
+    parse_patterns(input)
+        .unwrap()
+        .iter()
+        .map(|pattern| pattern.summary(0))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_solution() {
+        let input = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+        let result = solution(input);
+        let expected = 405;
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,79 @@
+use aoc_2023::day_13::{parse_patterns, Cell, Reflection};
+
+fn main() {
+    let input = include_str!("../../input/day_13");
+    let (result, smudges) = solution(input);
+    for (index, reflection, cells) in smudges {
+        let line = match reflection {
+            Reflection::Vertical(columns) => format!("vertical line after column {columns}"),
+            Reflection::Horizontal(rows) => format!("horizontal line after row {rows}"),
+        };
+        let cells = cells
+            .iter()
+            .map(|cell| format!("row {}, column {}", cell.row + 1, cell.column + 1))
+            .collect::<Vec<_>>()
+            .join("; ");
+        eprintln!("pattern {}: {line}, smudge at {cells}", index + 1);
+    }
+    println!("{result}");
+}
+
+// The summary of every pattern with exactly one smudge, and for each pattern
+// (by index) the line it reflects across and where its smudge is.
+fn solution(input: &str) -> (usize, Vec<(usize, Reflection, Vec<Cell>)>) {
+    let patterns = parse_patterns(input).unwrap();
+    let smudges = patterns
+        .iter()
+        .enumerate()
+        .flat_map(|(index, pattern)| {
+            pattern
+                .reflections(1)
+                .into_iter()
+                .map(move |reflection| (index, reflection, pattern.smudges(reflection)))
+        })
+        .collect();
+    let summary = patterns.iter().map(|pattern| pattern.summary(1)).sum();
+
+    (summary, smudges)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_solution() {
+        let input = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+        let result = solution(input);
+        let expected = (
+            400,
+            vec![
+                (
+                    0,
+                    Reflection::Horizontal(3),
+                    vec![Cell { row: 0, column: 0 }],
+                ),
+                (
+                    1,
+                    Reflection::Horizontal(1),
+                    vec![Cell { row: 0, column: 4 }],
+                ),
+            ],
+        );
+        assert_eq!(result, expected);
+    }
+}
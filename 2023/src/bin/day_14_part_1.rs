@@ -0,0 +1,35 @@
+use aoc_2023::day_14::{Direction, Platform};
+
+fn main() {
+    let input = include_str!("../../input/day_14");
+    let result = solution(input);
+    println!("{result}");
+}
+
+fn solution(input: &str) -> usize {
+    let mut platform = Platform::parse(input).unwrap();
+    platform.tilt(Direction::North);
+    platform.north_load()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_solution() {
+        let input = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+        let result = solution(input);
+        let expected = 136;
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,43 @@
+use aoc_2023::day_14::{Platform, Repetition};
+
+fn main() {
+    let input = include_str!("../../input/day_14");
+    let (result, repetition) = solution(input);
+    if let Some(Repetition { start, period }) = repetition {
+        eprintln!("repeats every {period} spin cycles after {start}");
+    }
+    println!("{result}");
+}
+
+fn solution(input: &str) -> (usize, Option<Repetition>) {
+    let (platform, repetition) = Platform::parse(input).unwrap().spin(1_000_000_000);
+    (platform.north_load(), repetition)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_solution() {
+        let input = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+        let result = solution(input);
+        let expected = (
+            64,
+            Some(Repetition {
+                start: 3,
+                period: 7,
+            }),
+        );
+        assert_eq!(result, expected);
+    }
+}
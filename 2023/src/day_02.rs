@@ -0,0 +1,592 @@
+use std::{
+    cmp::max,
+    fmt::{self, Display, Formatter},
+    iter,
+    str::FromStr,
+};
+
+use itertools::Itertools;
+use winnow::{
+    ascii::dec_uint,
+    combinator::{alt, cut_err, eof, fail, preceded, repeat, separated_pair, terminated},
+    error::{ContextError, StrContext, StrContextValue},
+    stream::Stream,
+    PResult, Parser,
+};
+
+// A collection of games (ie. the whole puzzle input), one per line.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Games {
+    pub games: Vec<Game>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Cubes>,
+}
+
+// A set of cubes, used both for a single round drawn from the bag and for the
+// contents of the bag itself.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Cubes {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+// Error returned when input can't be parsed. Points at the (1-based) line and
+// column where parsing failed and describes what was expected there.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl FromStr for Games {
+    type Err = ParseError;
+
+    // Parse every line of input into a `Game`, reporting the line number of
+    // the first one that fails. Blank lines at the end are ignored.
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let games = str
+            .trim_end_matches(['\r', '\n'])
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                line.parse().map_err(|error| ParseError {
+                    line: index + 1,
+                    ..error
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Games { games })
+    }
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    // Parse a whole serialized game (eg. "Game 1: 3 blue, 4 red; 1 red")
+    // into a `Game` struct, failing unless the entire line is consumed.
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        parse_line(Game::parse, str)
+    }
+}
+
+impl FromStr for Cubes {
+    type Err = ParseError;
+
+    // Parse a whole serialized round (eg. "12 red, 13 green, 14 blue") into a
+    // `Cubes` struct, failing unless the entire string is consumed.
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        parse_line(Cubes::parse, str)
+    }
+}
+
+// Run `parser` over a single line, requiring it to consume everything, and
+// convert any failure into a `ParseError`.
+fn parse_line<'a, O>(
+    parser: impl Parser<&'a str, O, ContextError>,
+    input: &'a str,
+) -> Result<O, ParseError> {
+    terminated(
+        parser,
+        eof.context(StrContext::Expected(StrContextValue::Description(
+            "end of line",
+        ))),
+    )
+    .parse(input)
+    .map_err(|error| ParseError {
+        line: 1,
+        column: error.offset() + 1,
+        expected: error
+            .inner()
+            .context()
+            .filter_map(|context| match context {
+                StrContext::Expected(value) => Some(value.to_string()),
+                _ => None,
+            })
+            .join(", "),
+    })
+}
+
+// The space that must follow a `,` or `;` separator.
+fn parse_space(input: &mut &str) -> PResult<char> {
+    ' '.context(StrContext::Expected(' '.into()))
+        .parse_next(input)
+}
+
+impl Games {
+    // The minimum bag needed for each game, keyed by game ID.
+    pub fn minimum_cubes(&self) -> impl Iterator<Item = (u32, Cubes)> + '_ {
+        self.games
+            .iter()
+            .map(|game| (game.id, game.minimum_cubes()))
+    }
+
+    // The power of each game's minimum bag, keyed by game ID.
+    pub fn powers(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.games.iter().map(|game| (game.id, game.power()))
+    }
+
+    // The most cubes of each color drawn in any single round of any game.
+    // This is also the smallest bag under which every game is possible.
+    pub fn max_draws(&self) -> Cubes {
+        self.games
+            .iter()
+            .map(Game::minimum_cubes)
+            .fold(Cubes::empty(), |acc, cubes| acc.max(&cubes))
+    }
+
+    // The games that are possible if the bag contained `bag`.
+    pub fn possible_games<'a>(&'a self, bag: &'a Cubes) -> impl Iterator<Item = &'a Game> {
+        self.games.iter().filter(|game| game.is_possible(bag))
+    }
+
+    // For each game (keyed by ID), the indexes of the `bags` it's possible
+    // under.
+    pub fn possible_bags<'a>(
+        &'a self,
+        bags: &'a [Cubes],
+    ) -> impl Iterator<Item = (u32, Vec<usize>)> + 'a {
+        self.games.iter().map(|game| {
+            let indexes = bags.iter().positions(|bag| game.is_possible(bag)).collect();
+            (game.id, indexes)
+        })
+    }
+}
+
+impl Game {
+    // Parse an input string slice into `Game`. Anything following a separator
+    // is wrapped in `cut_err` so a malformed round is reported where it
+    // breaks rather than backtracking to the last complete round.
+    fn parse(input: &mut &str) -> PResult<Game> {
+        let (id, first, rest): (_, _, Vec<Cubes>) = (
+            preceded(
+                "Game ".context(StrContext::Expected("Game ".into())),
+                dec_uint.context(StrContext::Expected(StrContextValue::Description(
+                    "game id",
+                ))),
+            ),
+            preceded(
+                ": ".context(StrContext::Expected(": ".into())),
+                cut_err(Cubes::parse),
+            ),
+            repeat(
+                0..,
+                preceded(';', cut_err(preceded(parse_space, Cubes::parse))),
+            ),
+        )
+            .parse_next(input)?;
+
+        Ok(Game {
+            id,
+            rounds: iter::once(first).chain(rest).collect(),
+        })
+    }
+
+    // The smallest bag this game is possible with. The game is possible with
+    // exactly those bags that contain at least these cubes.
+    pub fn minimum_cubes(&self) -> Cubes {
+        // Iterate over each round building a new "minumum" cubes set.
+        // Start with an empty set and create a new set by by fining
+        // the max of each individual color comparing to the current
+        // round.
+        self.rounds
+            .iter()
+            .fold(Cubes::empty(), |acc, round| acc.max(round))
+    }
+
+    pub fn power(&self) -> u32 {
+        self.minimum_cubes().power()
+    }
+
+    pub fn is_possible(&self, bag: &Cubes) -> bool {
+        bag.contains(&self.minimum_cubes())
+    }
+}
+
+impl Cubes {
+    // Parse a round (eg. "1 green, 3 red, 6 blue") into a `Cubes` struct
+    // (eg. `Cubes { red: 3, green: 1, blue: 6 }`), rejecting rounds that
+    // draw a color more than once.
+    fn parse(input: &mut &str) -> PResult<Cubes> {
+        let start = input.checkpoint();
+        let (first, rest): (_, Vec<(u32, Color)>) = (
+            Cubes::parse_draw,
+            repeat(
+                0..,
+                preceded(',', cut_err(preceded(parse_space, Cubes::parse_draw))),
+            ),
+        )
+            .parse_next(input)?;
+        let draws: Vec<(u32, Color)> = iter::once(first).chain(rest).collect();
+
+        // Report repeated colors at the start of the round.
+        if !draws.iter().map(|&(_, color)| color).all_unique() {
+            input.reset(start);
+            return cut_err(
+                fail.context(StrContext::Expected(StrContextValue::Description(
+                    "distinct colors",
+                ))),
+            )
+            .parse_next(input);
+        }
+
+        // Set each color's count from the draws in this round.
+        Ok(draws
+            .into_iter()
+            .fold(Cubes::empty(), |mut cubes, (count, color)| {
+                match color {
+                    Color::Red => cubes.red = count,
+                    Color::Green => cubes.green = count,
+                    Color::Blue => cubes.blue = count,
+                }
+                cubes
+            }))
+    }
+
+    // Parse a single draw (eg. "3 red") into a count color pair. Counts must
+    // be non-zero so every draw survives serializing with `Display`.
+    fn parse_draw(input: &mut &str) -> PResult<(u32, Color)> {
+        separated_pair(
+            dec_uint
+                .verify(|&count: &u32| count > 0)
+                .context(StrContext::Expected(StrContextValue::Description(
+                    "non-zero cube count",
+                ))),
+            ' '.context(StrContext::Expected(' '.into())),
+            Color::parse,
+        )
+        .parse_next(input)
+    }
+
+    pub fn empty() -> Self {
+        Cubes {
+            red: 0,
+            green: 0,
+            blue: 0,
+        }
+    }
+
+    pub fn power(&self) -> u32 {
+        self.red * self.green * self.blue
+    }
+
+    // Whether this set has at least as many cubes of every color as `other`.
+    pub fn contains(&self, other: &Cubes) -> bool {
+        self.red >= other.red && self.green >= other.green && self.blue >= other.blue
+    }
+
+    // The color-wise maximum of two sets.
+    pub fn max(&self, other: &Cubes) -> Cubes {
+        Cubes {
+            red: max(self.red, other.red),
+            green: max(self.green, other.green),
+            blue: max(self.blue, other.blue),
+        }
+    }
+}
+
+impl Color {
+    // Parse an input string slice into `Color`.
+    fn parse(input: &mut &str) -> PResult<Color> {
+        alt((
+            "red".value(Color::Red),
+            "green".value(Color::Green),
+            "blue".value(Color::Blue),
+        ))
+        .context(StrContext::Expected("red".into()))
+        .context(StrContext::Expected("green".into()))
+        .context(StrContext::Expected("blue".into()))
+        .parse_next(input)
+    }
+}
+
+impl Display for Game {
+    // Serialize back into the puzzle input format.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}: {}", self.id, self.rounds.iter().join("; "))
+    }
+}
+
+impl Display for Cubes {
+    // Serialize the non-zero counts in red, green, blue order
+    // (eg. "3 red, 6 blue"). Parsed rounds never draw zero cubes or repeat a
+    // color, so they serialize to a string that parses back to them.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let draws = [
+            (self.red, "red"),
+            (self.green, "green"),
+            (self.blue, "blue"),
+        ]
+        .into_iter()
+        .filter(|&(count, _)| count != 0)
+        .map(|(count, color)| format!("{count} {color}"))
+        .join(", ");
+
+        f.write_str(&draws)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    const BAG: Cubes = Cubes {
+        red: 12,
+        green: 13,
+        blue: 14,
+    };
+
+    #[rstest]
+    #[case(Cubes { red: 4, green: 0, blue: 3 }, true)]
+    #[case(Cubes { red: 20, green: 8, blue: 6 }, false)]
+    fn test_cubes_contains(#[case] round: Cubes, #[case] expected: bool) {
+        assert_eq!(BAG.contains(&round), expected);
+    }
+
+    #[rstest]
+    #[case(
+        Game { id: 1, rounds: vec![
+            Cubes { red: 4, green: 0, blue: 3 },
+            Cubes { red: 1, green: 2, blue: 6 }
+        ] },
+        true
+    )]
+    #[case(
+        Game { id: 2, rounds: vec![
+            Cubes { red: 20, green: 8, blue: 6 },
+            Cubes { red: 1, green: 2, blue: 6 }
+        ] },
+        false
+    )]
+    fn test_game_is_possible(#[case] game: Game, #[case] expected: bool) {
+        assert_eq!(game.is_possible(&BAG), expected);
+    }
+
+    #[rstest]
+    #[case("3 blue, 4 red", Cubes { red: 4, green: 0, blue: 3 })]
+    #[case("1 red, 2 green", Cubes { red: 1, green: 2, blue: 0 })]
+    #[case("5 blue, 4 red, 13 green", Cubes { red: 4, green: 13, blue: 5 })]
+    fn test_cubes_parse(#[case] mut input: &str, #[case] expected: Cubes) {
+        assert_eq!(Cubes::parse(&mut input).unwrap(), expected);
+        assert_eq!(input, "");
+    }
+
+    #[rstest]
+    #[case(
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+        Game { id: 1, rounds: vec![
+            Cubes { red: 4, green: 0, blue: 3 },
+            Cubes { red: 1, green: 2, blue: 6 },
+            Cubes { red: 0, green: 2, blue: 0 },
+        ] }
+    )]
+    fn test_game_from_str(#[case] input: &str, #[case] expected: Game) {
+        assert_eq!(input.parse::<Game>().unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("Gme 1: 3 blue", 1, "`Game `")]
+    #[case("Game one: 3 blue", 6, "game id")]
+    #[case("Game 1 3 blue", 7, "`: `")]
+    #[case("Game 1: 3 blue, 4 purple", 19, "`red`, `green`, `blue`")]
+    #[case("Game 1: 3 blue; ", 17, "non-zero cube count")]
+    #[case("Game 1: 0 red; 3 blue", 9, "non-zero cube count")]
+    #[case("Game 1: 3 blue; 1 red, 0 green", 24, "non-zero cube count")]
+    #[case("Game 1: 3 red, 4 red", 9, "distinct colors")]
+    #[case("Game 1: 1 blue; 2 green, 1 red, 3 green", 17, "distinct colors")]
+    #[case("Game 1: 3 blue,4 red", 16, "` `")]
+    #[case("Game 1: 3 red;1 blue", 15, "` `")]
+    #[case("Game 1: 4 red;", 15, "` `")]
+    #[case("Game 1: 4 red,", 15, "` `")]
+    #[case("Game 1: 4 red and", 14, "end of line")]
+    #[case("Game 1: 3blue", 10, "` `")]
+    fn test_game_from_str_error(
+        #[case] input: &str,
+        #[case] column: usize,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            input.parse::<Game>(),
+            Err(ParseError {
+                line: 1,
+                column,
+                expected: expected.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_games_from_str_trailing_blank_lines() {
+        let input = format!("{INPUT}\n\n");
+        assert_eq!(input.parse::<Games>(), INPUT.parse::<Games>());
+        assert_eq!(
+            "Game 1: 3 blue\n\nGame 2: 4 red".parse::<Games>(),
+            Err(ParseError {
+                line: 2,
+                column: 1,
+                expected: "`Game `".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_games_from_str_error() {
+        let input = "Game 1: 3 blue
+Game 2: 4 red;
+Game 3: 1 green";
+        let error = input.parse::<Games>().unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 15: expected ` `");
+    }
+
+    #[rstest]
+    #[case("Game 1: 4 red, 3 blue; 1 red, 2 green, 6 blue; 2 green")]
+    #[case("Game 100: 20 red, 8 green, 6 blue")]
+    #[case("Game 1: 1 red; 3 blue")]
+    fn test_game_display(#[case] input: &str) {
+        let game = input.parse::<Game>().unwrap();
+        assert_eq!(game.to_string(), input);
+        assert_eq!(game.to_string().parse::<Game>().unwrap(), game);
+    }
+
+    #[rstest]
+    #[case("Game 1: 3 blue, 4 red", "Game 1: 4 red, 3 blue")]
+    #[case(
+        "Game 2: 6 blue, 2 green, 1 red; 1 green",
+        "Game 2: 1 red, 2 green, 6 blue; 1 green"
+    )]
+    fn test_game_display_reorders(#[case] input: &str, #[case] expected: &str) {
+        let game = input.parse::<Game>().unwrap();
+        assert_eq!(game.to_string(), expected);
+        assert_eq!(game.to_string().parse::<Game>().unwrap(), game);
+    }
+
+    // Inputs that would not survive a round trip are rejected.
+    #[rstest]
+    #[case("Game 1: 0 red; 3 blue")]
+    #[case("Game 1: 3 red, 4 red")]
+    fn test_game_display_unrepresentable(#[case] input: &str) {
+        assert!(input.parse::<Game>().is_err());
+    }
+
+    #[test]
+    fn test_games_minimum_cubes_and_powers() {
+        let games: Games = INPUT.parse().unwrap();
+        assert_eq!(
+            games.minimum_cubes().collect_vec(),
+            vec![
+                (
+                    1,
+                    Cubes {
+                        red: 4,
+                        green: 2,
+                        blue: 6
+                    }
+                ),
+                (
+                    2,
+                    Cubes {
+                        red: 1,
+                        green: 3,
+                        blue: 4
+                    }
+                ),
+                (
+                    3,
+                    Cubes {
+                        red: 20,
+                        green: 13,
+                        blue: 6
+                    }
+                ),
+                (
+                    4,
+                    Cubes {
+                        red: 14,
+                        green: 3,
+                        blue: 15
+                    }
+                ),
+                (
+                    5,
+                    Cubes {
+                        red: 6,
+                        green: 3,
+                        blue: 2
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            games.powers().collect_vec(),
+            vec![(1, 48), (2, 12), (3, 1560), (4, 630), (5, 36)]
+        );
+    }
+
+    #[test]
+    fn test_games_max_draws() {
+        let games: Games = INPUT.parse().unwrap();
+        assert_eq!(
+            games.max_draws(),
+            Cubes {
+                red: 20,
+                green: 13,
+                blue: 15,
+            }
+        );
+        assert_eq!(games.possible_games(&games.max_draws()).count(), 5);
+    }
+
+    #[test]
+    fn test_games_possible_bags() {
+        let games: Games = INPUT.parse().unwrap();
+        let bags = [
+            BAG,
+            Cubes {
+                red: 20,
+                green: 13,
+                blue: 6,
+            },
+        ];
+        assert_eq!(
+            games.possible_bags(&bags).collect_vec(),
+            vec![
+                (1, vec![0, 1]),
+                (2, vec![0, 1]),
+                (3, vec![1]),
+                (4, vec![]),
+                (5, vec![0, 1]),
+            ]
+        );
+    }
+}
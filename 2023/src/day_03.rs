@@ -0,0 +1,781 @@
+use std::{
+    cmp::min,
+    collections::HashSet,
+    fmt::{self, Display, Formatter, Write},
+};
+
+use itertools::Itertools;
+
+use winnow::{
+    ascii::digit1, combinator::alt, stream::Location, token::none_of, Located, PResult, Parser,
+};
+
+// Engine schematic parsed into row and column coordinates.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Schematic {
+    // Number of rows and the length of the longest row. Rows may be ragged.
+    pub row_count: usize,
+    pub column_count: usize,
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub row: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Symbol {
+    pub position: Position,
+    pub char: char,
+}
+
+// A number spanning `length` columns of a row starting at `position`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Number {
+    pub position: Position,
+    pub length: usize,
+    pub value: u32,
+}
+
+// Error returned when a number in the schematic doesn't fit in a `u32`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    // Position of the number's first digit.
+    pub position: Position,
+    pub token: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "row {}, column {}: `{}` is too large",
+            self.position.row + 1,
+            self.position.column + 1,
+            self.token
+        )
+    }
+}
+
+// An inclusive rectangle of positions.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BoundingBox {
+    pub top: usize,
+    pub left: usize,
+    pub bottom: usize,
+    pub right: usize,
+}
+
+// Rules deciding which numbers are part numbers and which symbols are gears.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Rules {
+    // Characters that count as symbols for part numbers, `None` meaning any
+    // character other than a digit or `.`.
+    pub symbols: Option<Vec<char>>,
+    // Characters that can be gears. These needn't also be in `symbols`.
+    pub gears: Vec<char>,
+    // Exact number of adjacent numbers a gear needs.
+    pub gear_number_count: usize,
+    // Whether diagonal positions count as adjacent.
+    pub diagonal: bool,
+}
+
+// How a position is highlighted when rendering an annotated schematic.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Annotation {
+    PartNumber,
+    NonPartNumber,
+    Symbol,
+    Gear,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    // Colours using ANSI escape codes, for terminals.
+    Ansi,
+    // A line of markers under each row (`P` part number, `n` non-part number,
+    // `s` symbol, `G` gear), for anywhere colours aren't available.
+    Plain,
+    // A standalone HTML document.
+    Html,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Symbol(Symbol),
+    Number(Number),
+    Dot,
+}
+
+impl Schematic {
+    // Parse the schematic line by line. Line endings (`\n` or `\r\n`) are
+    // never part of a row, so they can't be mistaken for symbols.
+    pub fn parse(input: &str) -> Result<Schematic, ParseError> {
+        let mut schematic = Schematic {
+            row_count: 0,
+            column_count: 0,
+            numbers: vec![],
+            symbols: vec![],
+        };
+
+        for (row, line) in input.lines().enumerate() {
+            schematic.row_count += 1;
+            schematic.column_count = schematic.column_count.max(line.chars().count());
+
+            // Wrap in `Located` so parsers can find the column within the line.
+            // `Located` counts bytes, so columns are converted to characters
+            // to stay correct after multibyte symbols.
+            let mut located_line = Located::new(line);
+            let char_column = |byte_column: usize| line[..byte_column].chars().count();
+            while !located_line.is_empty() {
+                match parse_token(row, &mut located_line) {
+                    Ok(Token::Number(mut number)) => {
+                        number.position.column = char_column(number.position.column);
+                        schematic.numbers.push(number);
+                    }
+                    Ok(Token::Symbol(mut symbol)) => {
+                        symbol.position.column = char_column(symbol.position.column);
+                        schematic.symbols.push(symbol);
+                    }
+                    Ok(Token::Dot) => {}
+                    // Any other character is a symbol, so only a number that
+                    // doesn't fit can fail to parse.
+                    Err(_) => {
+                        let byte_column = located_line.location();
+                        return Err(ParseError {
+                            position: Position {
+                                row,
+                                column: char_column(byte_column),
+                            },
+                            token: line[byte_column..]
+                                .chars()
+                                .take_while(char::is_ascii_digit)
+                                .collect(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(schematic)
+    }
+
+    // The box of positions around `number` (including the number itself),
+    // clamped to the bounds of the schematic.
+    pub fn neighbourhood(&self, number: &Number) -> BoundingBox {
+        let bounding_box = number.bounding_box();
+
+        BoundingBox {
+            top: bounding_box.top.saturating_sub(1),
+            left: bounding_box.left.saturating_sub(1),
+            bottom: min(bounding_box.bottom + 1, self.row_count.saturating_sub(1)),
+            right: min(bounding_box.right + 1, self.column_count.saturating_sub(1)),
+        }
+    }
+
+    // Whether `position` touches `number`, diagonally too if `rules` allow.
+    pub fn is_adjacent(&self, number: &Number, position: Position, rules: &Rules) -> bool {
+        let bounding_box = number.bounding_box();
+
+        self.neighbourhood(number).contains(position)
+            && (rules.diagonal
+                || position.row == bounding_box.top
+                || (bounding_box.left..=bounding_box.right).contains(&position.column))
+    }
+
+    // Symbols (as defined by `rules`) adjacent to `number`.
+    pub fn adjacent_symbols<'a>(
+        &'a self,
+        number: &'a Number,
+        rules: &'a Rules,
+    ) -> impl Iterator<Item = &'a Symbol> {
+        self.symbols.iter().filter(move |symbol| {
+            rules.is_symbol(symbol.char) && self.is_adjacent(number, symbol.position, rules)
+        })
+    }
+
+    // Numbers adjacent to `symbol`.
+    pub fn adjacent_numbers<'a>(
+        &'a self,
+        symbol: &'a Symbol,
+        rules: &'a Rules,
+    ) -> impl Iterator<Item = &'a Number> {
+        self.numbers
+            .iter()
+            .filter(move |number| self.is_adjacent(number, symbol.position, rules))
+    }
+
+    // Numbers adjacent to any symbol.
+    pub fn part_numbers<'a>(&'a self, rules: &'a Rules) -> impl Iterator<Item = &'a Number> {
+        self.numbers
+            .iter()
+            .filter(|number| self.adjacent_symbols(number, rules).next().is_some())
+    }
+
+    // Gear symbols together with their adjacent numbers, only including those
+    // adjacent to exactly the number of numbers `rules` require.
+    pub fn gears<'a>(
+        &'a self,
+        rules: &'a Rules,
+    ) -> impl Iterator<Item = (&'a Symbol, Vec<&'a Number>)> {
+        self.symbols
+            .iter()
+            .filter(|symbol| rules.is_gear(symbol.char))
+            .filter_map(|symbol| {
+                let numbers: Vec<&Number> = self.adjacent_numbers(symbol, rules).collect();
+                if numbers.len() == rules.gear_number_count {
+                    Some((symbol, numbers))
+                } else {
+                    None
+                }
+            })
+    }
+
+    // Product of the numbers adjacent to each gear.
+    pub fn gear_ratios<'a>(&'a self, rules: &'a Rules) -> impl Iterator<Item = u32> + 'a {
+        self.gears(rules)
+            .map(|(_, numbers)| numbers.iter().map(|number| number.value).product())
+    }
+}
+
+impl Rules {
+    pub fn is_symbol(&self, char: char) -> bool {
+        match &self.symbols {
+            Some(symbols) => symbols.contains(&char),
+            None => true,
+        }
+    }
+
+    pub fn is_gear(&self, char: char) -> bool {
+        self.gears.contains(&char)
+    }
+}
+
+impl Default for Rules {
+    // The rules from the puzzle: any character other than a digit or `.` is
+    // a symbol, and a gear is a `*` touching exactly two numbers, with
+    // diagonals counting as adjacent.
+    fn default() -> Self {
+        Rules {
+            symbols: None,
+            gears: vec!['*'],
+            gear_number_count: 2,
+            diagonal: true,
+        }
+    }
+}
+
+impl Schematic {
+    // Every row of the schematic as characters paired with how they are
+    // annotated under `rules`. Rows are padded with `.` to `column_count`, and
+    // anything positioned outside the schematic's bounds is left out.
+    pub fn annotated_rows(&self, rules: &Rules) -> Vec<Vec<(char, Option<Annotation>)>> {
+        let mut rows = vec![vec![('.', None); self.column_count]; self.row_count];
+
+        let part_numbers: HashSet<&Number> = self.part_numbers(rules).collect();
+        for number in &self.numbers {
+            let annotation = if part_numbers.contains(number) {
+                Annotation::PartNumber
+            } else {
+                Annotation::NonPartNumber
+            };
+            let digits = number.value.to_string();
+            for (offset, char) in digits.chars().enumerate() {
+                let position = Position {
+                    row: number.position.row,
+                    column: number.position.column + offset,
+                };
+                if let Some(cell) = cell_mut(&mut rows, position) {
+                    *cell = (char, Some(annotation));
+                }
+            }
+        }
+
+        let gears: HashSet<Position> = self
+            .gears(rules)
+            .map(|(symbol, _)| symbol.position)
+            .collect();
+        for symbol in &self.symbols {
+            let annotation = if gears.contains(&symbol.position) {
+                Some(Annotation::Gear)
+            } else if rules.is_symbol(symbol.char) {
+                Some(Annotation::Symbol)
+            } else {
+                None
+            };
+            if let Some(cell) = cell_mut(&mut rows, symbol.position) {
+                *cell = (symbol.char, annotation);
+            }
+        }
+
+        rows
+    }
+
+    // Render the schematic with part numbers, non-part numbers, symbols and
+    // gears distinguished, followed by a list of gears and their ratios.
+    pub fn render(&self, rules: &Rules, format: Format) -> String {
+        let rows = self.annotated_rows(rules);
+        let gears: Vec<String> = self
+            .gears(rules)
+            .map(|(symbol, numbers)| {
+                format!(
+                    "gear {} at row {}, column {}: {} = {}",
+                    symbol.char,
+                    symbol.position.row + 1,
+                    symbol.position.column + 1,
+                    numbers.iter().map(|number| number.value).join(" * "),
+                    numbers.iter().map(|number| number.value).product::<u32>(),
+                )
+            })
+            .collect();
+
+        let mut output = String::new();
+        match format {
+            Format::Ansi => {
+                for row in rows {
+                    for (char, annotation) in row {
+                        match annotation.map(Annotation::ansi_code) {
+                            Some(code) => write!(output, "\x1b[{code}m{char}\x1b[0m").unwrap(),
+                            None => output.push(char),
+                        }
+                    }
+                    output.push('\n');
+                }
+            }
+            Format::Plain => {
+                // Each row is followed by a line of markers under it.
+                for row in rows {
+                    output.extend(row.iter().map(|&(char, _)| char));
+                    output.push('\n');
+                    let markers: String = row
+                        .iter()
+                        .map(|&(_, annotation)| annotation.map_or(' ', Annotation::marker))
+                        .collect();
+                    output.push_str(markers.trim_end());
+                    output.push('\n');
+                }
+            }
+            Format::Html => {
+                output.push_str(HTML_HEADER);
+                for row in rows {
+                    for (char, annotation) in row {
+                        let char = html_escape(char);
+                        match annotation {
+                            Some(annotation) => write!(
+                                output,
+                                "<span class=\"{}\">{char}</span>",
+                                annotation.class()
+                            )
+                            .unwrap(),
+                            None => output.push_str(&char),
+                        }
+                    }
+                    output.push('\n');
+                }
+                output.push_str("</pre>\n<pre>\n");
+            }
+        }
+
+        output.push('\n');
+        for gear in gears {
+            match format {
+                Format::Html => output.push_str(&html_escape_str(&gear)),
+                _ => output.push_str(&gear),
+            }
+            output.push('\n');
+        }
+        if format == Format::Html {
+            output.push_str("</pre>\n</body>\n</html>\n");
+        }
+
+        output
+    }
+}
+
+impl Annotation {
+    fn ansi_code(self) -> &'static str {
+        match self {
+            Annotation::PartNumber => "32",
+            Annotation::NonPartNumber => "31",
+            Annotation::Symbol => "33",
+            Annotation::Gear => "1;35",
+        }
+    }
+
+    fn marker(self) -> char {
+        match self {
+            Annotation::PartNumber => 'P',
+            Annotation::NonPartNumber => 'n',
+            Annotation::Symbol => 's',
+            Annotation::Gear => 'G',
+        }
+    }
+
+    fn class(self) -> &'static str {
+        match self {
+            Annotation::PartNumber => "part",
+            Annotation::NonPartNumber => "non-part",
+            Annotation::Symbol => "symbol",
+            Annotation::Gear => "gear",
+        }
+    }
+}
+
+const HTML_HEADER: &str = "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Day 3 schematic</title>
+<style>
+body { background: #0f0f23; color: #666; }
+.part { color: #00cc00; }
+.non-part { color: #ff4444; }
+.symbol { color: #ffff66; }
+.gear { color: #ff66ff; font-weight: bold; }
+</style>
+</head>
+<body>
+<pre>
+";
+
+fn html_escape(char: char) -> String {
+    match char {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        '"' => "&quot;".to_string(),
+        _ => char.to_string(),
+    }
+}
+
+fn html_escape_str(str: &str) -> String {
+    str.chars().map(html_escape).collect()
+}
+
+impl Number {
+    // The positions the digits of this number occupy.
+    pub fn bounding_box(&self) -> BoundingBox {
+        BoundingBox {
+            top: self.position.row,
+            left: self.position.column,
+            bottom: self.position.row,
+            right: self.position.column + self.length - 1,
+        }
+    }
+}
+
+impl BoundingBox {
+    pub fn contains(&self, position: Position) -> bool {
+        (self.top..=self.bottom).contains(&position.row)
+            && (self.left..=self.right).contains(&position.column)
+    }
+}
+
+fn cell_mut<T>(rows: &mut [Vec<T>], position: Position) -> Option<&mut T> {
+    rows.get_mut(position.row)
+        .and_then(|row| row.get_mut(position.column))
+}
+
+fn parse_token(row: usize, input: &mut Located<&str>) -> PResult<Token> {
+    alt((
+        |input: &mut Located<&str>| parse_number(row, input),
+        parse_dot,
+        |input: &mut Located<&str>| parse_symbol(row, input),
+    ))
+    .parse_next(input)
+}
+
+fn parse_symbol(row: usize, input: &mut Located<&str>) -> PResult<Token> {
+    let column = input.location();
+    let char =
+        none_of(['.', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9']).parse_next(input)?;
+
+    Ok(Token::Symbol(Symbol {
+        position: Position { row, column },
+        char,
+    }))
+}
+
+fn parse_number(row: usize, input: &mut Located<&str>) -> PResult<Token> {
+    let column = input.location();
+    let (value, length) = digit1
+        .try_map(|digits: &str| digits.parse().map(|value| (value, digits.len())))
+        .parse_next(input)?;
+
+    Ok(Token::Number(Number {
+        position: Position { row, column },
+        length,
+        value,
+    }))
+}
+
+fn parse_dot(input: &mut Located<&str>) -> PResult<Token> {
+    '.'.parse_next(input)?;
+
+    Ok(Token::Dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use winnow::stream::Stream;
+
+    use super::*;
+
+    fn number(row: usize, column: usize, length: usize, value: u32) -> Number {
+        Number {
+            position: Position { row, column },
+            length,
+            value,
+        }
+    }
+
+    fn symbol(row: usize, column: usize, char: char) -> Symbol {
+        Symbol {
+            position: Position { row, column },
+            char,
+        }
+    }
+
+    #[test]
+    fn test_parse_symbol() {
+        let input = "*.....";
+        let mut located_input = Located::new(input);
+        let output = parse_symbol(3, &mut located_input).unwrap();
+        assert_eq!(output, Token::Symbol(symbol(3, 0, '*')));
+        assert_eq!(located_input.finish(), ".....");
+    }
+
+    #[test]
+    fn test_parse_number() {
+        let input = "1234.....";
+        let mut located_input = Located::new(input);
+        let output = parse_number(3, &mut located_input).unwrap();
+        assert_eq!(output, Token::Number(number(3, 0, 4, 1234)));
+        assert_eq!(located_input.finish(), ".....");
+    }
+
+    #[test]
+    fn test_parse_dot() {
+        let input = "....1234";
+        let mut located_input = Located::new(input);
+        let output = parse_dot(&mut located_input).unwrap();
+        assert_eq!(output, Token::Dot);
+        assert_eq!(located_input.finish(), "...1234");
+    }
+
+    #[rstest]
+    #[case("467..114..\n...*......\n..35.#633.")]
+    #[case("467..114..\r\n...*......\r\n..35.#633.\r\n")]
+    fn test_parse_schematic(#[case] input: &str) {
+        let output = Schematic::parse(input).unwrap();
+        assert_eq!(
+            output,
+            Schematic {
+                row_count: 3,
+                column_count: 10,
+                numbers: vec![
+                    number(0, 0, 3, 467),
+                    number(0, 5, 3, 114),
+                    number(2, 2, 2, 35),
+                    number(2, 6, 3, 633),
+                ],
+                symbols: vec![symbol(1, 3, '*'), symbol(2, 5, '#')],
+            }
+        );
+    }
+
+    #[rstest]
+    #[case("12345678901*", 0, 0, "12345678901")]
+    #[case("1..\n.é99999999999", 1, 2, "99999999999")]
+    #[case("*4294967296", 0, 1, "4294967296")]
+    fn test_parse_schematic_error(
+        #[case] input: &str,
+        #[case] row: usize,
+        #[case] column: usize,
+        #[case] token: &str,
+    ) {
+        assert_eq!(
+            Schematic::parse(input),
+            Err(ParseError {
+                position: Position { row, column },
+                token: token.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_schematic_error_message() {
+        let error = Schematic::parse("..\n.12345678901").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "row 2, column 2: `12345678901` is too large"
+        );
+    }
+
+    #[test]
+    fn test_parse_schematic_largest_number() {
+        let schematic = Schematic::parse("4294967295").unwrap();
+        assert_eq!(schematic.numbers, vec![number(0, 0, 10, u32::MAX)]);
+    }
+
+    #[rstest]
+    #[case(number(0, 5, 3, 114), BoundingBox { top: 0, left: 4, bottom: 1, right: 8 })]
+    #[case(number(5, 0, 1, 4), BoundingBox { top: 4, left: 0, bottom: 6, right: 1 })]
+    #[case(number(4, 3, 4, 6666), BoundingBox { top: 3, left: 2, bottom: 5, right: 7 })]
+    #[case(number(9, 8, 2, 88), BoundingBox { top: 8, left: 7, bottom: 9, right: 9 })]
+    fn test_schematic_neighbourhood(#[case] number: Number, #[case] expected: BoundingBox) {
+        let schematic = Schematic {
+            row_count: 10,
+            column_count: 10,
+            numbers: vec![],
+            symbols: vec![],
+        };
+        assert_eq!(schematic.neighbourhood(&number), expected);
+    }
+
+    #[rstest]
+    // Symbol at the end of the row above a number at the start of a row.
+    #[case("....#\n12...", vec![])]
+    // Number at the end of a row, symbol at the start of the next.
+    #[case("...12\n#....", vec![])]
+    // Diagonals touching both ends of a number on the edges.
+    #[case("#...\n.12.\n...#", vec![12])]
+    #[case("12..\n..*.", vec![12])]
+    #[case("..12\n.*..", vec![12])]
+    // Ragged rows: the symbol is beyond the end of the shorter row.
+    #[case("1\n.#\n..", vec![1])]
+    #[case("..1\n#", vec![])]
+    // Multibyte symbols take a single column.
+    #[case("é1", vec![1])]
+    #[case("é.1", vec![])]
+    #[case("€€.\n..1", vec![1])]
+    #[case("€€€.\n....1", vec![])]
+    fn test_schematic_part_numbers_edges(#[case] input: &str, #[case] expected: Vec<u32>) {
+        let schematic = Schematic::parse(input).unwrap();
+        let result: Vec<u32> = schematic
+            .part_numbers(&Rules::default())
+            .map(|number| number.value)
+            .collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_schematic_gear_ratios() {
+        let input = "467..114..\r
+...*......\r
+..35..633.\r
+......#...\r
+617*......\r
+.....+.58.\r
+..592.....\r
+......755.\r
+...$.*....\r
+.664.598..\r
+";
+        let schematic = Schematic::parse(input).unwrap();
+        let result: Vec<u32> = schematic.gear_ratios(&Rules::default()).collect();
+        assert_eq!(result, vec![16345, 451490]);
+    }
+
+    const VARIANT_INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[rstest]
+    #[case(Rules::default(), 4361)]
+    #[case(Rules { symbols: Some(vec!['*']), ..Rules::default() }, 467 + 35 + 617 + 755 + 598)]
+    #[case(Rules { symbols: Some(vec!['#', '+']), ..Rules::default() }, 633 + 592)]
+    #[case(Rules { diagonal: false, ..Rules::default() }, 35 + 633 + 617 + 664 + 598)]
+    fn test_schematic_part_numbers_rules(#[case] rules: Rules, #[case] expected: u32) {
+        let schematic = Schematic::parse(VARIANT_INPUT).unwrap();
+        let result: u32 = schematic
+            .part_numbers(&rules)
+            .map(|number| number.value)
+            .sum();
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(Rules::default(), vec![16345, 451490])]
+    #[case(Rules { gear_number_count: 1, ..Rules::default() }, vec![617])]
+    #[case(Rules { gears: vec!['*', '#', '$'], ..Rules::default() }, vec![16345, 451490])]
+    #[case(Rules { gears: vec!['#', '$'], gear_number_count: 1, ..Rules::default() }, vec![633, 664])]
+    #[case(Rules { diagonal: false, ..Rules::default() }, vec![])]
+    fn test_schematic_gear_ratios_rules(#[case] rules: Rules, #[case] expected: Vec<u32>) {
+        let schematic = Schematic::parse(VARIANT_INPUT).unwrap();
+        let result: Vec<u32> = schematic.gear_ratios(&rules).collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_schematic_render_multibyte() {
+        let schematic = Schematic::parse("é1\n..").unwrap();
+        let result = schematic.render(&Rules::default(), Format::Plain);
+        assert_eq!(result, "é1\nsP\n..\n\n\n");
+    }
+
+    #[test]
+    fn test_schematic_render_out_of_bounds() {
+        let mut schematic = Schematic::parse("1*\n..").unwrap();
+        schematic.numbers.push(number(5, 0, 1, 7));
+        schematic.symbols.push(Symbol {
+            position: Position { row: 0, column: 9 },
+            char: '#',
+        });
+        let result = schematic.render(&Rules::default(), Format::Plain);
+        assert_eq!(result, "1*\nPs\n..\n\n\n");
+    }
+
+    #[test]
+    fn test_schematic_render_plain() {
+        let schematic = Schematic::parse("467..114..\n...*......\n..35..633.\n......#...").unwrap();
+        let result = schematic.render(&Rules::default(), Format::Plain);
+        let expected = "467..114..
+PPP  nnn
+...*......
+   G
+..35..633.
+  PP  PPP
+......#...
+      s
+
+gear * at row 2, column 4: 467 * 35 = 16345
+";
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_schematic_render_ansi() {
+        let schematic = Schematic::parse("1.2\n#..").unwrap();
+        let result = schematic.render(&Rules::default(), Format::Ansi);
+        let expected = "\x1b[32m1\x1b[0m.\x1b[31m2\x1b[0m\n\x1b[33m#\x1b[0m..\n\n";
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_schematic_render_html() {
+        let schematic = Schematic::parse("1&2\n...").unwrap();
+        let rules = Rules {
+            gears: vec!['&'],
+            ..Rules::default()
+        };
+        let result = schematic.render(&rules, Format::Html);
+        assert!(result.starts_with("<!DOCTYPE html>"));
+        assert!(result.contains(
+            "<span class=\"part\">1</span><span class=\"gear\">&amp;</span><span class=\"part\">2</span>\n...\n"
+        ));
+        assert!(result.contains("gear &amp; at row 1, column 2: 1 * 2 = 2\n"));
+        assert!(result.ends_with("</html>\n"));
+    }
+}
@@ -0,0 +1,396 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+};
+
+use itertools::Itertools;
+use winnow::{
+    ascii::{dec_uint, line_ending, space0, space1},
+    combinator::{cut_err, opt, preceded, repeat, terminated},
+    error::{ContextError, ParseError, StrContext, StrContextValue},
+    PResult, Parser,
+};
+
+use crate::parsing::trailing_lines;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Scratchcard {
+    pub id: u32,
+    pub winning: NumberSet,
+    pub owned: NumberSet,
+}
+
+// Set of card numbers. Numbers below 128 (all of them in the puzzle input)
+// are stored as bits of a `u128`, so intersecting two sets is a single `&`.
+// Anything larger falls back to a `HashSet`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct NumberSet {
+    bits: u128,
+    large: HashSet<u32>,
+}
+
+// How many copies of a card were ended up with, and which earlier cards
+// they were won from.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CardCopies {
+    pub id: u32,
+    // Total copies including the original.
+    pub copies: u64,
+    // Pairs of source card ID and the number of copies won from it.
+    pub won_from: Vec<(u32, u64)>,
+}
+
+// What to do when a card's matches would win copies of cards past the end of
+// the table.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OverflowPolicy {
+    // Fail with `CascadeOverflow`.
+    Error,
+    // Discard the copies that would fall past the end.
+    Discard,
+}
+
+// Error returned when the cascade of won copies can't be played out.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CascadeOverflow {
+    // A card wins copies past the end of the table under
+    // `OverflowPolicy::Error`.
+    PastEnd {
+        id: u32,
+        match_count: usize,
+        // Number of cards after this one in the table.
+        remaining: usize,
+    },
+    // A card's number of copies doesn't fit in a `u64`.
+    Copies {
+        id: u32,
+    },
+}
+
+impl Display for CascadeOverflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CascadeOverflow::PastEnd {
+                id,
+                match_count,
+                remaining,
+            } => write!(
+                f,
+                "card {id} has {match_count} matches but only {remaining} cards follow it"
+            ),
+            CascadeOverflow::Copies { id } => {
+                write!(f, "card {id} has more than {} copies", u64::MAX)
+            }
+        }
+    }
+}
+
+// Parse every line of input into a `Scratchcard`.
+pub fn parse_scratchcards(input: &str) -> Result<Vec<Scratchcard>, ParseError<&str, ContextError>> {
+    terminated(repeat(0.., Scratchcard::parse), trailing_lines).parse(input)
+}
+
+impl Scratchcard {
+    // Parse an input string slice (eg. "Card 1: 41 48 | 83 86  6") into
+    // `Scratchcard`, rejecting cards that repeat a number on either side.
+    // Everything after "Card" is wrapped in `cut_err` so errors are reported
+    // where they occur rather than where the card started.
+    pub fn parse(input: &mut &str) -> PResult<Scratchcard> {
+        let (_, (_, id, _, winning, _, owned, _, _)) = (
+            "Card".context(StrContext::Expected("Card".into())),
+            cut_err((
+                space1,
+                dec_uint.context(StrContext::Expected(StrContextValue::Description(
+                    "card id",
+                ))),
+                ':'.context(StrContext::Expected(':'.into())),
+                Scratchcard::parse_numbers,
+                preceded(space1, '|').context(StrContext::Expected('|'.into())),
+                Scratchcard::parse_numbers,
+                space0,
+                opt(line_ending),
+            )),
+        )
+            .parse_next(input)?;
+
+        Ok(Scratchcard { id, winning, owned })
+    }
+
+    // Parse a whitespace separated list of distinct numbers into `NumberSet`.
+    fn parse_numbers(input: &mut &str) -> PResult<NumberSet> {
+        repeat(1.., preceded(space1, dec_uint::<_, u32, _>))
+            .verify(|numbers: &Vec<u32>| numbers.iter().all_unique())
+            .context(StrContext::Expected(StrContextValue::Description(
+                "distinct numbers",
+            )))
+            .map(|numbers: Vec<u32>| numbers.into_iter().collect())
+            .parse_next(input)
+    }
+
+    // Count the owned numbers that are also winning numbers.
+    pub fn match_count(&self) -> usize {
+        self.winning.intersection_count(&self.owned)
+    }
+
+    // Points are 1 for the first match, doubling for each match after.
+    pub fn points(&self) -> u32 {
+        match self.match_count() {
+            0 => 0,
+            match_count => 2_u32.pow(match_count as u32 - 1),
+        }
+    }
+}
+
+// Play the cards in table order: each copy of a card with `n` matches wins a
+// copy of each of the next `n` cards. Returns the copies of every card.
+pub fn simulate(
+    scratchcards: &[Scratchcard],
+    overflow: OverflowPolicy,
+) -> Result<Vec<CardCopies>, CascadeOverflow> {
+    let mut cards: Vec<CardCopies> = scratchcards
+        .iter()
+        .map(|scratchcard| CardCopies {
+            id: scratchcard.id,
+            copies: 1,
+            won_from: vec![],
+        })
+        .collect();
+
+    for (index, scratchcard) in scratchcards.iter().enumerate() {
+        let match_count = scratchcard.match_count();
+        let remaining = scratchcards.len() - index - 1;
+        if match_count > remaining && overflow == OverflowPolicy::Error {
+            return Err(CascadeOverflow::PastEnd {
+                id: scratchcard.id,
+                match_count,
+                remaining,
+            });
+        }
+
+        // All copies of this card are final by now since only earlier cards
+        // can win copies of it.
+        let copies = cards[index].copies;
+        let end = index + 1 + match_count.min(remaining);
+        for card in &mut cards[(index + 1)..end] {
+            card.copies = card
+                .copies
+                .checked_add(copies)
+                .ok_or(CascadeOverflow::Copies { id: card.id })?;
+            card.won_from.push((scratchcard.id, copies));
+        }
+    }
+
+    Ok(cards)
+}
+
+impl Display for CardCopies {
+    // Explain where the copies came from (eg. "card 3: 4 copies (1 original,
+    // 1 from card 1, 2 from card 2)").
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "card {}: {} copies (1 original", self.id, self.copies)?;
+        for (id, copies) in &self.won_from {
+            write!(f, ", {copies} from card {id}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl NumberSet {
+    // Insert `number`, returning whether it wasn't already present.
+    pub fn insert(&mut self, number: u32) -> bool {
+        if number < u128::BITS {
+            let bit = 1 << number;
+            let inserted = self.bits & bit == 0;
+            self.bits |= bit;
+            inserted
+        } else {
+            self.large.insert(number)
+        }
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        if number < u128::BITS {
+            self.bits & (1 << number) != 0
+        } else {
+            self.large.contains(&number)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize + self.large.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Count the numbers in both sets.
+    pub fn intersection_count(&self, other: &NumberSet) -> usize {
+        (self.bits & other.bits).count_ones() as usize
+            + self.large.intersection(&other.large).count()
+    }
+}
+
+impl FromIterator<u32> for NumberSet {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        let mut set = NumberSet::default();
+        for number in iter {
+            set.insert(number);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_scratchcard_parse() {
+        let mut input = "Card   1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19";
+        let result = Scratchcard::parse(&mut input).unwrap();
+        assert_eq!(
+            result,
+            Scratchcard {
+                id: 1,
+                winning: NumberSet::from_iter([41, 48, 83, 86, 17]),
+                owned: NumberSet::from_iter([83, 86, 6, 31, 17, 9, 48, 53]),
+            }
+        );
+        assert_eq!(input, "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19");
+    }
+
+    #[test]
+    fn test_parse_scratchcards_trailing_blank_lines() {
+        let result = parse_scratchcards("Card 1: 41 | 41\nCard 2: 83 | 86\n\n").unwrap();
+        assert_eq!(result.len(), 2);
+    }
+
+    #[rstest]
+    #[case("Card 1: 41 48 41 | 83 86")]
+    #[case("Card 1: 41 48 | 83 86 83")]
+    #[case("Card 1: 41 48 83 86")]
+    #[case("Card one: 41 | 83")]
+    #[case("Card 1: 41 | 83\n\nCard 2: 41 | 83")]
+    fn test_parse_scratchcards_error(#[case] input: &str) {
+        assert!(parse_scratchcards(input).is_err());
+    }
+
+    #[test]
+    fn test_parse_scratchcards_error_message() {
+        let input = "Card 1: 41 48 | 83 86
+Card 2: 13 32 13 | 61 30";
+        let error = parse_scratchcards(input).unwrap_err();
+        assert_eq!(error.offset(), 29);
+        assert_eq!(error.inner().to_string(), "expected distinct numbers");
+    }
+
+    #[rstest]
+    #[case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", 4, 8)]
+    #[case("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1", 2, 2)]
+    #[case("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83", 1, 1)]
+    #[case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36", 0, 0)]
+    #[case("Card 7: 127 128 1000 3 | 1000 3 127 129", 3, 4)]
+    fn test_scratchcard_match_count_and_points(
+        #[case] mut input: &str,
+        #[case] match_count: usize,
+        #[case] points: u32,
+    ) {
+        let scratchcard = Scratchcard::parse(&mut input).unwrap();
+        assert_eq!(scratchcard.match_count(), match_count);
+        assert_eq!(scratchcard.points(), points);
+    }
+
+    #[test]
+    fn test_number_set() {
+        let mut set = NumberSet::default();
+        assert!(set.is_empty());
+        assert!(set.insert(0));
+        assert!(set.insert(127));
+        assert!(set.insert(128));
+        assert!(set.insert(99999));
+        assert!(!set.insert(127));
+        assert!(!set.insert(99999));
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(0) && set.contains(127) && set.contains(128));
+        assert!(!set.contains(1) && !set.contains(129));
+    }
+
+    #[test]
+    fn test_simulate() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        let scratchcards = parse_scratchcards(input).unwrap();
+        let result = simulate(&scratchcards, OverflowPolicy::Error).unwrap();
+        assert_eq!(
+            result.iter().map(|card| card.copies).collect::<Vec<_>>(),
+            vec![1, 2, 4, 8, 14, 1]
+        );
+        assert_eq!(result[3].won_from, vec![(1, 1), (2, 2), (3, 4)]);
+        assert_eq!(
+            result[4].to_string(),
+            "card 5: 14 copies (1 original, 1 from card 1, 4 from card 3, 8 from card 4)"
+        );
+        assert_eq!(result[5].to_string(), "card 6: 1 copies (1 original)");
+    }
+
+    #[rstest]
+    #[case(
+        OverflowPolicy::Error,
+        Err(CascadeOverflow::PastEnd { id: 2, match_count: 2, remaining: 1 })
+    )]
+    #[case(OverflowPolicy::Discard, Ok(vec![1, 2, 3]))]
+    fn test_simulate_overflow(
+        #[case] overflow: OverflowPolicy,
+        #[case] expected: Result<Vec<u64>, CascadeOverflow>,
+    ) {
+        let input = "Card 1: 1 | 1
+Card 2: 1 2 | 1 2
+Card 3: 1 | 2";
+        let scratchcards = parse_scratchcards(input).unwrap();
+        let result = simulate(&scratchcards, overflow)
+            .map(|cards| cards.iter().map(|card| card.copies).collect());
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(OverflowPolicy::Error)]
+    #[case(OverflowPolicy::Discard)]
+    fn test_simulate_too_many_copies(#[case] overflow: OverflowPolicy) {
+        // Every card wins a copy of each of the next 10, so copies grow
+        // faster than doubling.
+        let input = (1..=100)
+            .map(|id| format!("Card {id}: 1 2 3 4 5 6 7 8 9 10 | 1 2 3 4 5 6 7 8 9 10"))
+            .join("\n");
+        let scratchcards = parse_scratchcards(&input).unwrap();
+        assert_eq!(
+            simulate(&scratchcards[..90], overflow).map(|cards| cards.len()),
+            Err(CascadeOverflow::Copies { id: 66 })
+        );
+        let error = simulate(&scratchcards, OverflowPolicy::Discard).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "card 66 has more than 18446744073709551615 copies"
+        );
+    }
+
+    #[test]
+    fn test_cascade_overflow_message() {
+        let error = CascadeOverflow::PastEnd {
+            id: 2,
+            match_count: 2,
+            remaining: 1,
+        };
+        assert_eq!(
+            error.to_string(),
+            "card 2 has 2 matches but only 1 cards follow it"
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::num::ParseIntError;
+
+use num::BigUint;
+use winnow::{
+    ascii::{digit1, line_ending, space0, space1},
+    combinator::{preceded, repeat},
+    error::{ContextError, ParseError, StrContext, StrContextValue},
+    PResult, Parser,
+};
+
+use crate::parsing::trailing_lines;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Record {
+    pub time: u128,
+    pub distance: u128,
+}
+
+// Parse the race sheet into one race per column.
+pub fn parse_races(input: &str) -> Result<Vec<Record>, ParseError<&str, ContextError>> {
+    parse_sheet
+        .try_map(|(times, distances)| {
+            times
+                .into_iter()
+                .zip(distances)
+                .map(|(time, distance)| {
+                    Ok(Record {
+                        time: time.parse()?,
+                        distance: distance.parse()?,
+                    })
+                })
+                .collect::<Result<_, ParseIntError>>()
+        })
+        .parse(input)
+}
+
+// Parse the race sheet as a single race, ignoring the spaces between the
+// columns of each line (ie. "bad kerning").
+pub fn parse_kerned_race(input: &str) -> Result<Record, ParseError<&str, ContextError>> {
+    parse_sheet
+        .try_map(|(times, distances)| {
+            Ok::<_, ParseIntError>(Record {
+                time: times.concat().parse()?,
+                distance: distances.concat().parse()?,
+            })
+        })
+        .parse(input)
+}
+
+// Parse the "Time:" and "Distance:" lines into the digits of each column,
+// requiring both lines to have the same number of columns.
+fn parse_sheet<'a>(input: &mut &'a str) -> PResult<(Vec<&'a str>, Vec<&'a str>)> {
+    let (_, times, _, _, _, distances, _, _) = (
+        "Time:".context(StrContext::Expected("Time:".into())),
+        parse_columns,
+        space0,
+        line_ending.context(StrContext::Expected('\n'.into())),
+        "Distance:".context(StrContext::Expected("Distance:".into())),
+        parse_columns,
+        space0,
+        trailing_lines,
+    )
+        .verify(|(_, times, _, _, _, distances, _, _)| times.len() == distances.len())
+        .context(StrContext::Expected(StrContextValue::Description(
+            "a distance for every time",
+        )))
+        .parse_next(input)?;
+
+    Ok((times, distances))
+}
+
+fn parse_columns<'a>(input: &mut &'a str) -> PResult<Vec<&'a str>> {
+    repeat(1.., preceded(space1, digit1))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "numbers",
+        )))
+        .parse_next(input)
+}
+
+impl Record {
+    // Count the hold times that travel strictly further than the record.
+    //
+    // Holding for `h` travels `h * (time - h)`, so winning hold times are the
+    // integers strictly between the roots of `h² - time·h + distance = 0`,
+    // ie. `(time ± √(time² - 4·distance)) / 2`. The discriminant needs up to
+    // 256 bits so it's computed as a `BigUint`. Its integer square root gives
+    // a first hold time within a step or two of the lower root, which is then
+    // nudged onto the exact boundary by checking the race directly.
+    pub fn ways_to_beat(&self) -> u128 {
+        // Distance is maximised holding for half the time. If that doesn't
+        // beat the record nothing will.
+        if !self.beats(self.time / 2) {
+            return 0;
+        }
+
+        let discriminant = BigUint::from(self.time).pow(2) - BigUint::from(self.distance) * 4_u32;
+        let root: u128 = discriminant.sqrt().try_into().unwrap();
+
+        // Find the shortest winning hold time.
+        let mut shortest = (self.time - root) / 2;
+        while shortest > 0 && self.beats(shortest - 1) {
+            shortest -= 1;
+        }
+        while !self.beats(shortest) {
+            shortest += 1;
+        }
+
+        // Winning hold times are symmetric around half the time, so the
+        // longest is `time - shortest`.
+        self.time - 2 * shortest + 1
+    }
+
+    // Whether holding for `hold_time` travels further than the record.
+    // Distances too large for `u128` certainly do.
+    fn beats(&self, hold_time: u128) -> bool {
+        hold_time
+            .checked_mul(self.time - hold_time)
+            .is_none_or(|distance| distance > self.distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "Time:      7  15   30
+Distance:  9  40  200
+";
+
+    #[test]
+    fn test_parse_races() {
+        let result = parse_races(INPUT).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                Record {
+                    time: 7,
+                    distance: 9,
+                },
+                Record {
+                    time: 15,
+                    distance: 40,
+                },
+                Record {
+                    time: 30,
+                    distance: 200,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_trailing_blank_lines() {
+        let input = format!("{INPUT}\n\n");
+        assert_eq!(parse_races(&input), parse_races(INPUT));
+        assert_eq!(parse_kerned_race(&input), parse_kerned_race(INPUT));
+    }
+
+    #[test]
+    fn test_parse_kerned_race() {
+        let result = parse_kerned_race(INPUT).unwrap();
+        assert_eq!(
+            result,
+            Record {
+                time: 71530,
+                distance: 940200,
+            }
+        );
+    }
+
+    #[rstest]
+    #[case("Time: 7 15\nDistance: 9")]
+    #[case("Time: 7 15\nDistance: 9 40 200")]
+    #[case("Time: 7 x\nDistance: 9 40")]
+    #[case("Distance: 9 40\nTime: 7 15")]
+    #[case("Time: 7 15 Distance: 9 40")]
+    fn test_parse_races_error(#[case] input: &str) {
+        assert!(parse_races(input).is_err());
+    }
+
+    #[test]
+    fn test_parse_kerned_race_overflow() {
+        let input = "Time: 1 00000000000000000000000000000000000000000
+Distance: 1 2";
+        assert!(parse_races(input).is_ok());
+        assert!(parse_kerned_race(input).is_err());
+    }
+
+    // The original implementation, trying every hold time.
+    fn ways_to_beat_brute_force(record: &Record) -> u128 {
+        (0..=record.time)
+            .filter(|hold_time| hold_time * (record.time - hold_time) > record.distance)
+            .count() as u128
+    }
+
+    #[rstest]
+    #[case(Record { time: 7, distance: 9 }, 4)]
+    #[case(Record { time: 15, distance: 40 }, 8)]
+    #[case(Record { time: 30, distance: 200 }, 9)]
+    #[case(Record { time: 71530, distance: 940200 }, 71503)]
+    // Ties with the record don't count.
+    #[case(Record { time: 10, distance: 25 }, 0)]
+    #[case(Record { time: 10, distance: 24 }, 1)]
+    #[case(Record { time: 10, distance: 21 }, 3)]
+    #[case(Record { time: 0, distance: 0 }, 0)]
+    #[case(Record { time: 2, distance: 0 }, 1)]
+    fn test_record_ways_to_beat(#[case] record: Record, #[case] expected: u128) {
+        assert_eq!(record.ways_to_beat(), expected);
+    }
+
+    #[test]
+    fn test_record_ways_to_beat_matches_brute_force() {
+        for time in 0..100 {
+            for distance in 0..=(time * time / 4 + 1) {
+                let record = Record { time, distance };
+                assert_eq!(
+                    record.ways_to_beat(),
+                    ways_to_beat_brute_force(&record),
+                    "{record:?}"
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(Record { time: u128::MAX, distance: 0 }, u128::MAX - 1)]
+    #[case(Record { time: u128::MAX, distance: u128::MAX }, u128::MAX - 3)]
+    #[case(Record { time: 1 << 64, distance: 1 << 126 }, 0)]
+    #[case(Record { time: 1 << 64, distance: (1 << 126) - 1 }, 1)]
+    #[case(Record { time: (1 << 64) + 2, distance: (1 << 126) + (1 << 64) }, 1)]
+    fn test_record_ways_to_beat_u128(#[case] record: Record, #[case] expected: u128) {
+        assert_eq!(record.ways_to_beat(), expected);
+    }
+}
@@ -0,0 +1,388 @@
+use std::fmt::{self, Display, Formatter};
+
+use itertools::Itertools;
+use num::{BigInt, One, Zero};
+
+// Error returned when a sequence line contains something other than an
+// integer.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    // 1-based line number.
+    pub line: usize,
+    pub token: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: `{}` is not an integer", self.line, self.token)
+    }
+}
+
+// Parse each line of input into a sequence of whitespace separated integers.
+pub fn parse_sequences(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            line.split_whitespace()
+                .map(|token| {
+                    token.parse().map_err(|_| ParseError {
+                        line: index + 1,
+                        token: token.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Sum of the next value of every sequence (part 1).
+pub fn forward(sequences: &[Vec<i64>]) -> Result<i64, Overflow> {
+    sequences.iter().try_fold(0_i64, |sum, values| {
+        sum.checked_add(next_value(values)?).ok_or(Overflow)
+    })
+}
+
+// Sum of the value before the first of every sequence (part 2).
+pub fn backward(sequences: &[Vec<i64>]) -> Result<i64, Overflow> {
+    sequences.iter().try_fold(0_i64, |sum, values| {
+        sum.checked_add(previous_value(values)?).ok_or(Overflow)
+    })
+}
+
+// The value after the last one. `Overflow` is only reported when the value
+// itself doesn't fit in an `i64`.
+pub fn next_value(values: &[i64]) -> Result<i64, Overflow> {
+    Polynomial::fit(values).evaluate(values.len())
+}
+
+// The value before the first one.
+pub fn previous_value(values: &[i64]) -> Result<i64, Overflow> {
+    Polynomial::fit(values).evaluate(-1)
+}
+
+// The lowest degree polynomial through a sequence's values (taken at
+// `x = 0, 1, 2, ...`) in Newton forward difference form,
+// ie. `p(x) = Σ differences[k] · C(x, k)`. The differences are kept as
+// `BigInt`s so fitting and evaluating are exact for any integer type.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Polynomial {
+    // The first value of each level of differences.
+    pub differences: Vec<BigInt>,
+}
+
+impl Polynomial {
+    pub fn fit<T>(values: &[T]) -> Polynomial
+    where
+        T: Clone + Into<BigInt>,
+    {
+        let mut differences = vec![];
+        let mut level: Vec<BigInt> = values.iter().cloned().map(Into::into).collect();
+        while let Some(first) = level.first() {
+            differences.push(first.clone());
+            level = level.iter().tuple_windows().map(|(a, b)| b - a).collect();
+        }
+
+        // Drop trailing zero differences, they contribute nothing.
+        while differences.last().is_some_and(BigInt::is_zero) {
+            differences.pop();
+        }
+
+        Polynomial { differences }
+    }
+
+    // Degree of the polynomial, treating the zero polynomial as degree 0.
+    pub fn degree(&self) -> usize {
+        self.differences.len().saturating_sub(1)
+    }
+
+    // The value at `x`, or `Overflow` if it doesn't fit in `T`.
+    pub fn evaluate<T>(&self, x: impl Into<BigInt>) -> Result<T, Overflow>
+    where
+        T: TryFrom<BigInt>,
+    {
+        let x = x.into();
+        let mut binomial = BigInt::one();
+        let mut result = BigInt::zero();
+        for (k, difference) in self.differences.iter().enumerate() {
+            if k > 0 {
+                // C(x, k) = C(x, k - 1) · (x - k + 1) / k, which divides
+                // exactly.
+                binomial = binomial * (&x - (k - 1)) / k;
+            }
+            result += difference * &binomial;
+        }
+
+        T::try_from(result).map_err(|_| Overflow)
+    }
+}
+
+// Error returned when an extrapolated value doesn't fit in the integer type
+// being used.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Overflow;
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "extrapolation overflowed")
+    }
+}
+
+// The value `steps` places after the last one (`steps = 1` being the next
+// value), for any integer type including `BigInt`. `Overflow` is only
+// reported when the predicted value itself doesn't fit in `T` (eg. a negative
+// prediction for an unsigned type).
+pub fn predict_ahead<T>(values: &[T], steps: u64) -> Result<T, Overflow>
+where
+    T: Clone + Into<BigInt> + TryFrom<BigInt>,
+{
+    let last_index = BigInt::from(values.len()) - 1;
+    Polynomial::fit(values).evaluate(last_index + steps)
+}
+
+// The value `steps` places before the first one (`steps = 1` being the
+// previous value).
+pub fn predict_behind<T>(values: &[T], steps: u64) -> Result<T, Overflow>
+where
+    T: Clone + Into<BigInt> + TryFrom<BigInt>,
+{
+    Polynomial::fit(values).evaluate(-BigInt::from(steps))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use num::BigInt;
+
+    use super::*;
+
+    const INPUT: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    // Recursively extrapolate the next value of a sequence by taking
+    // differences until they are all 0. Kept as an oracle for `Polynomial`.
+    fn extrapolate(values: Vec<i64>) -> i64 {
+        // Base case when all values are 0, extrapolation is 0.
+        if values.iter().all(|&value| value == 0) {
+            0
+        } else {
+            // Child values are calculated by the difference of each value
+            // pair.
+            let children = values.iter().tuple_windows().map(|(a, b)| b - a).collect();
+            // Add the last value to the recursive extrapolation of the children
+            // to calucate this extrapolated value.
+            values.last().unwrap() + extrapolate(children)
+        }
+    }
+
+    #[test]
+    fn test_parse_sequences() {
+        let result = parse_sequences(INPUT).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                vec![0, 3, 6, 9, 12, 15],
+                vec![1, 3, 6, 10, 15, 21],
+                vec![10, 13, 16, 21, 30, 45],
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("1 2 3\n4 five 6", 2, "five")]
+    #[case("1 2 3.5", 1, "3.5")]
+    #[case("1 2\n3 4\n5 99999999999999999999", 3, "99999999999999999999")]
+    fn test_parse_sequences_error(#[case] input: &str, #[case] line: usize, #[case] token: &str) {
+        assert_eq!(
+            parse_sequences(input),
+            Err(ParseError {
+                line,
+                token: token.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_forward_and_backward() {
+        let sequences = parse_sequences(INPUT).unwrap();
+        assert_eq!(forward(&sequences), Ok(114));
+        assert_eq!(backward(&sequences), Ok(2));
+    }
+
+    #[test]
+    fn test_forward_and_backward_long_sequences() {
+        let sequences = vec![(0..200).collect(), vec![7; 300]];
+        assert_eq!(forward(&sequences), Ok(207));
+        assert_eq!(backward(&sequences), Ok(6));
+        assert_eq!(forward(&[vec![i64::MAX; 200]]), Ok(i64::MAX));
+        assert_eq!(
+            forward(&[(0..200).map(|i| i64::MAX - 199 + i).collect()]),
+            Err(Overflow)
+        );
+    }
+
+    #[rstest]
+    #[case(vec![0, 3, 6, 9, 12, 15], 18)]
+    #[case(vec![1, 3, 6, 10, 15, 21], 28)]
+    #[case(vec![10, 13, 16, 21, 30, 45], 68)]
+    fn test_extrapolate(#[case] values: Vec<i64>, #[case] expected: i64) {
+        assert_eq!(extrapolate(values.clone()), expected);
+        assert_eq!(next_value(&values), Ok(expected));
+    }
+
+    #[rstest]
+    #[case(vec![0, 3, 6, 9, 12, 15], -3)]
+    #[case(vec![1, 3, 6, 10, 15, 21], 0)]
+    #[case(vec![10, 13, 16, 21, 30, 45], 5)]
+    #[case(vec![7], 7)]
+    #[case(vec![], 0)]
+    fn test_previous_value(#[case] values: Vec<i64>, #[case] expected: i64) {
+        assert_eq!(previous_value(&values), Ok(expected));
+    }
+
+    #[rstest]
+    #[case(vec![0, 3, 6, 9, 12, 15], vec![0, 3], 1)]
+    #[case(vec![1, 3, 6, 10, 15, 21], vec![1, 2, 1], 2)]
+    #[case(vec![5, 5, 5], vec![5], 0)]
+    #[case(vec![0, 0], vec![], 0)]
+    fn test_polynomial_fit(
+        #[case] values: Vec<i64>,
+        #[case] differences: Vec<i64>,
+        #[case] degree: usize,
+    ) {
+        let polynomial = Polynomial::fit(&values);
+        let differences: Vec<BigInt> = differences.into_iter().map(BigInt::from).collect();
+        assert_eq!(polynomial.differences, differences);
+        assert_eq!(polynomial.degree(), degree);
+    }
+
+    #[rstest]
+    #[case(70)]
+    #[case(120)]
+    #[case(200)]
+    fn test_closed_form_long_sequences(#[case] length: i64) {
+        let values: Vec<i64> = (0..length).collect();
+        assert_eq!(next_value(&values), Ok(length));
+        assert_eq!(previous_value(&values), Ok(-1));
+    }
+
+    #[test]
+    fn test_closed_form_overflow() {
+        assert_eq!(next_value(&[i64::MAX - 1, i64::MAX]), Err(Overflow));
+        assert_eq!(previous_value(&[i64::MIN, i64::MIN + 1]), Err(Overflow));
+        assert_eq!(previous_value(&[i64::MIN, i64::MAX]), Err(Overflow));
+        assert_eq!(forward(&[vec![i64::MAX], vec![1]]), Err(Overflow));
+        let polynomial = Polynomial::fit(&[i64::MIN, i64::MAX]);
+        assert_eq!(polynomial.evaluate(1), Ok(i64::MAX));
+        assert_eq!(polynomial.evaluate::<i64>(2), Err(Overflow));
+    }
+
+    #[rstest]
+    #[case(vec![0, 1, 2], 1 << 40, Ok(1 << 40))]
+    #[case(vec![0, 1, 2], -5, Ok(-5))]
+    #[case(vec![0, 1, 4], 1 << 31, Ok(1 << 62))]
+    #[case(vec![0, 0, 1], i64::MAX, Err(Overflow))]
+    fn test_polynomial_evaluate(
+        #[case] values: Vec<i64>,
+        #[case] x: i64,
+        #[case] expected: Result<i64, Overflow>,
+    ) {
+        assert_eq!(Polynomial::fit(&values).evaluate(x), expected);
+    }
+
+    #[test]
+    fn test_closed_form_matches_recursive_on_input() {
+        let input = include_str!("../input/day_09");
+        for values in parse_sequences(input).unwrap() {
+            let line = values.iter().join(" ");
+            let reversed: Vec<i64> = values.iter().rev().copied().collect();
+            let polynomial = Polynomial::fit(&values);
+
+            assert_eq!(
+                next_value(&values),
+                Ok(extrapolate(values.clone())),
+                "{line}"
+            );
+            assert_eq!(previous_value(&values), Ok(extrapolate(reversed)), "{line}");
+            assert_eq!(
+                polynomial.evaluate(values.len() as i64),
+                next_value(&values)
+            );
+            assert_eq!(polynomial.evaluate(-1), previous_value(&values));
+            for (x, &value) in values.iter().enumerate() {
+                assert_eq!(polynomial.evaluate(x as i64), Ok(value), "{line}");
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(vec![1, 3, 6, 10, 15, 21], 0, Ok(21))]
+    #[case(vec![1, 3, 6, 10, 15, 21], 1, Ok(28))]
+    #[case(vec![1, 3, 6, 10, 15, 21], 3, Ok(45))]
+    #[case(vec![0, 3, 6, 9, 12, 15], 100, Ok(315))]
+    #[case(vec![], 5, Ok(0))]
+    #[case(vec![i64::MAX - 2, i64::MAX - 1], 1, Ok(i64::MAX))]
+    #[case(vec![i64::MAX - 2, i64::MAX - 1], 2, Err(Overflow))]
+    #[case(vec![i64::MIN, i64::MAX], 0, Ok(i64::MAX))]
+    #[case(vec![i64::MIN, i64::MAX], 1, Err(Overflow))]
+    #[case(vec![5, 5, 5, 5], 1 << 40, Ok(5))]
+    #[case(vec![0, 1, 2], 1 << 32, Ok((1 << 32) + 2))]
+    #[case(vec![0, 1, 4], u64::MAX, Err(Overflow))]
+    fn test_predict_ahead(
+        #[case] values: Vec<i64>,
+        #[case] steps: u64,
+        #[case] expected: Result<i64, Overflow>,
+    ) {
+        assert_eq!(predict_ahead(&values, steps), expected);
+    }
+
+    #[rstest]
+    #[case(vec![1, 3, 6, 10, 15, 21], 0, Ok(1))]
+    #[case(vec![1, 3, 6, 10, 15, 21], 1, Ok(0))]
+    #[case(vec![1, 3, 6, 10, 15, 21], 2, Ok(0))]
+    #[case(vec![1, 3, 6, 10, 15, 21], 3, Ok(1))]
+    #[case(vec![10, 13, 16, 21, 30, 45], 1, Ok(5))]
+    fn test_predict_behind(
+        #[case] values: Vec<i64>,
+        #[case] steps: u64,
+        #[case] expected: Result<i64, Overflow>,
+    ) {
+        assert_eq!(predict_behind(&values, steps), expected);
+    }
+
+    #[test]
+    fn test_predict_small_types() {
+        assert_eq!(predict_ahead(&[100_i8, 110, 120], 1), Err(Overflow));
+        assert_eq!(predict_ahead(&[1_u8, 2, 3], 1), Ok(4));
+        assert_eq!(predict_behind(&[1_u8, 2, 3], 1), Ok(0));
+        assert_eq!(predict_behind(&[1_u8, 2, 3], 2), Err(Overflow));
+        assert_eq!(predict_behind(&[3_u8, 2, 1], 1), Ok(4));
+        assert_eq!(predict_ahead(&[3_u8, 2, 1], 1), Ok(0));
+        assert_eq!(predict_ahead(&[250_u8, 0, 250], 1), Err(Overflow));
+        assert_eq!(predict_ahead(&[u64::MAX, u64::MAX], u64::MAX), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn test_predict_big_int() {
+        // Cubes of 0 to 4, predicted far beyond what fits in any primitive.
+        let values: Vec<BigInt> = (0..5).map(|i: i64| BigInt::from(i.pow(3))).collect();
+        let steps = u64::MAX - 4;
+        let x = BigInt::from(4) + BigInt::from(steps);
+        assert_eq!(predict_ahead(&values, steps), Ok(x.pow(3)));
+        assert_eq!(
+            predict_behind(&values, steps),
+            Ok(-BigInt::from(steps).pow(3))
+        );
+    }
+
+    #[test]
+    fn test_predict_matches_closed_form_on_input() {
+        let input = include_str!("../input/day_09");
+        for values in parse_sequences(input).unwrap() {
+            assert_eq!(predict_ahead(&values, 1), next_value(&values));
+            assert_eq!(predict_behind(&values, 1), previous_value(&values));
+        }
+    }
+}
@@ -0,0 +1,637 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter, Write},
+};
+
+use itertools::Itertools;
+
+pub struct Tiles {
+    grid: Vec<Vec<Tile>>,
+    start_location: Location,
+    // Locations on the main loop in order, starting with the start location.
+    main_loop: Vec<Location>,
+}
+
+// Error returned when the start tile or the loop through it is invalid.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TilesError {
+    // A character that isn't a tile, and where it is.
+    InvalidTile(char, Location),
+    MissingStart,
+    MultipleStarts(Location, Location),
+    // The start tile must connect to exactly two adjacent pipes that connect
+    // back to it.
+    StartConnections(Vec<Direction>),
+    // Following the loop from the start reached a location that doesn't
+    // continue it.
+    BrokenLoop(Location),
+}
+
+impl Display for TilesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TilesError::InvalidTile(tile, location) => {
+                write!(f, "invalid tile {tile:?} at {location}")
+            }
+            TilesError::MissingStart => write!(f, "no start tile"),
+            TilesError::MultipleStarts(first, second) => {
+                write!(f, "start tiles at both {first} and {second}")
+            }
+            TilesError::StartConnections(directions) => write!(
+                f,
+                "start tile connects to {} pipes, expected 2",
+                directions.len()
+            ),
+            TilesError::BrokenLoop(location) => write!(f, "loop is broken at {location}"),
+        }
+    }
+}
+
+impl Tiles {
+    // Parse the map, replacing the start tile with the pipe it must be given
+    // the pipes around it, and follow the main loop through it.
+    pub fn parse(input: &str) -> Result<Tiles, TilesError> {
+        let grid: Vec<Vec<Tile>> = input
+            .lines()
+            .enumerate()
+            .map(|(row_index, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(column_index, tile)| {
+                        Tile::parse(tile).ok_or(TilesError::InvalidTile(
+                            tile,
+                            Location(row_index, column_index),
+                        ))
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut start_locations = grid.iter().enumerate().flat_map(|(row_index, row)| {
+            row.iter()
+                .positions(|&tile| tile == Tile::Start)
+                .map(move |column_index| Location(row_index, column_index))
+        });
+        let start_location = start_locations.next().ok_or(TilesError::MissingStart)?;
+        if let Some(other) = start_locations.next() {
+            return Err(TilesError::MultipleStarts(start_location, other));
+        }
+
+        let mut tiles = Tiles {
+            grid,
+            start_location,
+            main_loop: vec![],
+        };
+
+        let start_connections = tiles.start_connections();
+        let start_tile = match start_connections.as_slice() {
+            &[first, second] => Tile::from_connections(first, second).unwrap(),
+            _ => return Err(TilesError::StartConnections(start_connections)),
+        };
+        tiles.grid[start_location.0][start_location.1] = start_tile;
+        tiles.main_loop = tiles.follow_loop()?;
+
+        Ok(tiles)
+    }
+
+    pub fn start_location(&self) -> Location {
+        self.start_location
+    }
+
+    // The locations on the main loop in order, starting with the start
+    // location.
+    pub fn main_loop(&self) -> &[Location] {
+        &self.main_loop
+    }
+
+    pub fn get(&self, location: Location) -> Option<Tile> {
+        self.grid
+            .get(location.0)
+            .and_then(|row| row.get(location.1).copied())
+    }
+
+    fn follow_loop(&self) -> Result<Vec<Location>, TilesError> {
+        let start_location = self.start_location;
+        let mut direction = self.get(start_location).unwrap().connections().unwrap()[0];
+        let mut location = start_location
+            .translate(direction)
+            .ok_or(TilesError::BrokenLoop(start_location))?;
+        let mut locations = vec![start_location];
+        while location != start_location {
+            locations.push(location);
+            (direction, location) = self
+                .get(location)
+                .and_then(|tile| tile.traverse(direction, location))
+                .ok_or(TilesError::BrokenLoop(location))?;
+        }
+
+        Ok(locations)
+    }
+
+    // Steps along the loop to the location farthest from the start.
+    pub fn farthest_distance(&self) -> u32 {
+        self.main_loop.len() as u32 / 2
+    }
+
+    // Count the tiles enclosed by the main loop.
+    //
+    // The loop's locations are the vertices of a polygon, whose area is given
+    // by the shoelace formula. Pick's theorem relates that area to the number
+    // of lattice points inside (`i`) and on the boundary (`b`) of the
+    // polygon, `area = i + b/2 - 1`, and every location on the loop is a
+    // boundary point.
+    pub fn enclosed_count(&self) -> u32 {
+        let locations = &self.main_loop;
+        let double_area = locations
+            .iter()
+            .zip(locations.iter().cycle().skip(1))
+            .map(|(a, b)| (a.0 * b.1) as i64 - (b.0 * a.1) as i64)
+            .sum::<i64>()
+            .unsigned_abs();
+
+        // Signed since the smallest loop (a 2x2 square) has a doubled area of 2
+        // but 4 boundary points.
+        ((double_area as i64 - locations.len() as i64) / 2 + 1) as u32
+    }
+
+    // The locations enclosed by the main loop, in row order. Scanning each
+    // row from the left, a location is inside the loop once an odd number of
+    // loop tiles connecting north have been crossed.
+    pub fn enclosed_locations(&self) -> Vec<Location> {
+        let main_loop: HashSet<Location> = self.main_loop.iter().copied().collect();
+
+        let mut locations = vec![];
+        for (row_index, row) in self.grid.iter().enumerate() {
+            let mut inside = false;
+            for (column_index, tile) in row.iter().enumerate() {
+                let location = Location(row_index, column_index);
+                if main_loop.contains(&location) {
+                    inside ^= tile.is_connected(Direction::North);
+                } else if inside {
+                    locations.push(location);
+                }
+            }
+        }
+
+        locations
+    }
+
+    // Redraw the map with box-drawing characters, distinguishing the main
+    // loop, the start tile, other pipes and enclosed tiles, followed by a
+    // summary line.
+    pub fn render(&self, format: Format) -> String {
+        let main_loop: HashSet<Location> = self.main_loop.iter().copied().collect();
+        let enclosed: HashSet<Location> = self.enclosed_locations().into_iter().collect();
+
+        let mut output = String::new();
+        for (row_index, row) in self.grid.iter().enumerate() {
+            for (column_index, tile) in row.iter().enumerate() {
+                let location = Location(row_index, column_index);
+                match format {
+                    Format::Ansi => {
+                        if location == self.start_location {
+                            write!(output, "\x1b[1;33m{}\x1b[0m", tile.box_drawing(false)).unwrap();
+                        } else if main_loop.contains(&location) {
+                            output.push(tile.box_drawing(false));
+                        } else if enclosed.contains(&location) {
+                            output.push_str("\x1b[32m●\x1b[0m");
+                        } else if *tile == Tile::Ground {
+                            output.push(' ');
+                        } else {
+                            write!(output, "\x1b[2m{}\x1b[0m", tile.box_drawing(false)).unwrap();
+                        }
+                    }
+                    Format::Plain => {
+                        if main_loop.contains(&location) {
+                            output.push(tile.box_drawing(true));
+                        } else if enclosed.contains(&location) {
+                            output.push('●');
+                        } else {
+                            output.push(tile.box_drawing(false));
+                        }
+                    }
+                }
+            }
+            output.push('\n');
+        }
+
+        writeln!(
+            output,
+            "\n{} tiles on the loop, {} enclosed",
+            self.main_loop.len(),
+            enclosed.len()
+        )
+        .unwrap();
+
+        output
+    }
+
+    // Determine the directions the start location is connected to.
+    fn start_connections(&self) -> Vec<Direction> {
+        Direction::iter()
+            .filter_map(|direction| {
+                self.start_location
+                    .translate(direction)
+                    .and_then(|adjacent_location| {
+                        self.get(adjacent_location).and_then(|adjacent_tile| {
+                            if adjacent_tile.is_connected(direction.inverse()) {
+                                Some(direction)
+                            } else {
+                                None
+                            }
+                        })
+                    })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    // Colours using ANSI escape codes, for terminals. The start tile is
+    // highlighted, pipes not on the main loop are dimmed and enclosed tiles
+    // are marked with `●`.
+    Ansi,
+    // Heavy lines for the main loop and light lines for other pipes, with
+    // enclosed tiles marked with `●`, for writing to files.
+    Plain,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Location(pub usize, pub usize);
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "row {}, column {}", self.0 + 1, self.1 + 1)
+    }
+}
+
+impl Location {
+    pub fn translate(&self, direction: Direction) -> Option<Location> {
+        let mut location = *self;
+        match direction {
+            Direction::North => {
+                if location.0 == 0 {
+                    return None;
+                } else {
+                    location.0 -= 1
+                }
+            }
+            Direction::South => location.0 += 1,
+            Direction::East => location.1 += 1,
+            Direction::West => {
+                if location.1 == 0 {
+                    return None;
+                } else {
+                    location.1 -= 1
+                }
+            }
+        }
+
+        Some(location)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    pub fn iter() -> impl Iterator<Item = Direction> {
+        [
+            Direction::North,
+            Direction::South,
+            Direction::East,
+            Direction::West,
+        ]
+        .iter()
+        .copied()
+    }
+
+    pub fn inverse(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Tile {
+    Vertical,
+    Horizontal,
+    NorthEastBend,
+    NorthWestBend,
+    SouthWestBend,
+    SouthEastBend,
+    Ground,
+    Start,
+}
+
+impl Tile {
+    pub fn parse(input: char) -> Option<Tile> {
+        match input {
+            '|' => Some(Tile::Vertical),
+            '-' => Some(Tile::Horizontal),
+            'L' => Some(Tile::NorthEastBend),
+            'J' => Some(Tile::NorthWestBend),
+            '7' => Some(Tile::SouthWestBend),
+            'F' => Some(Tile::SouthEastBend),
+            '.' => Some(Tile::Ground),
+            'S' => Some(Tile::Start),
+            _ => None,
+        }
+    }
+
+    // The pipe connecting the two directions, if they differ.
+    fn from_connections(first: Direction, second: Direction) -> Option<Tile> {
+        [
+            Tile::Vertical,
+            Tile::Horizontal,
+            Tile::NorthEastBend,
+            Tile::NorthWestBend,
+            Tile::SouthWestBend,
+            Tile::SouthEastBend,
+        ]
+        .into_iter()
+        .find(|tile| first != second && tile.is_connected(first) && tile.is_connected(second))
+    }
+
+    // The box-drawing character for a pipe, using heavy or light lines.
+    // Ground is blank.
+    fn box_drawing(&self, heavy: bool) -> char {
+        match (self, heavy) {
+            (Tile::Vertical, false) => '│',
+            (Tile::Vertical, true) => '┃',
+            (Tile::Horizontal, false) => '─',
+            (Tile::Horizontal, true) => '━',
+            (Tile::NorthEastBend, false) => '└',
+            (Tile::NorthEastBend, true) => '┗',
+            (Tile::NorthWestBend, false) => '┘',
+            (Tile::NorthWestBend, true) => '┛',
+            (Tile::SouthWestBend, false) => '┐',
+            (Tile::SouthWestBend, true) => '┓',
+            (Tile::SouthEastBend, false) => '┌',
+            (Tile::SouthEastBend, true) => '┏',
+            (Tile::Ground, _) => ' ',
+            (Tile::Start, _) => 'S',
+        }
+    }
+
+    // The directions a pipe connects to. Ground and an unresolved start tile
+    // have none.
+    pub fn connections(&self) -> Option<[Direction; 2]> {
+        match self {
+            Tile::Vertical => Some([Direction::North, Direction::South]),
+            Tile::Horizontal => Some([Direction::East, Direction::West]),
+            Tile::NorthEastBend => Some([Direction::North, Direction::East]),
+            Tile::NorthWestBend => Some([Direction::North, Direction::West]),
+            Tile::SouthWestBend => Some([Direction::South, Direction::West]),
+            Tile::SouthEastBend => Some([Direction::South, Direction::East]),
+            Tile::Ground | Tile::Start => None,
+        }
+    }
+
+    pub fn is_connected(&self, direction: Direction) -> bool {
+        self.connections()
+            .is_some_and(|connections| connections.contains(&direction))
+    }
+
+    // Pass through the pipe at `location`, having moved into it in
+    // `direction`. Returns `None` if the pipe doesn't connect back the way
+    // it was entered or leads off the top or left of the map.
+    pub fn traverse(
+        &self,
+        direction: Direction,
+        location: Location,
+    ) -> Option<(Direction, Location)> {
+        if !self.is_connected(direction.inverse()) {
+            return None;
+        }
+
+        let next_direction = self
+            .connections()?
+            .into_iter()
+            .find(|&d| d != direction.inverse())?;
+        let next_location = location.translate(next_direction)?;
+
+        Some((next_direction, next_location))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const SIMPLE: &str = "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+    const COMPLEX: &str = "7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ";
+
+    const ENCLOSED: &str = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    const SQUEEZED: &str = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+    const LARGER: &str = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    const JUNK: &str = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+    #[rstest]
+    #[case(SIMPLE, Tile::SouthEastBend)]
+    #[case(COMPLEX, Tile::SouthEastBend)]
+    #[case(ENCLOSED, Tile::SouthEastBend)]
+    #[case(LARGER, Tile::SouthEastBend)]
+    #[case(JUNK, Tile::SouthWestBend)]
+    #[case("F-7\n|.|\nLSJ", Tile::Horizontal)]
+    #[case("F-7\n|.|\nS-J", Tile::NorthEastBend)]
+    fn test_tiles_parse_start(#[case] input: &str, #[case] expected: Tile) {
+        let tiles = Tiles::parse(input).unwrap();
+        assert_eq!(tiles.get(tiles.start_location()), Some(expected));
+    }
+
+    #[test]
+    fn test_tiles_main_loop() {
+        let input = ".....
+.S-7.
+.|.|.
+.L-J.
+.....";
+        let tiles = Tiles::parse(input).unwrap();
+        assert_eq!(
+            tiles.main_loop(),
+            [
+                Location(1, 1),
+                Location(2, 1),
+                Location(3, 1),
+                Location(3, 2),
+                Location(3, 3),
+                Location(2, 3),
+                Location(1, 3),
+                Location(1, 2),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("S-7\n|x|\nL-J", TilesError::InvalidTile('x', Location(1, 1)))]
+    #[case("S-7\n|.|\nL-J ", TilesError::InvalidTile(' ', Location(2, 3)))]
+    #[case("S-7\n\t|.|\nL-J", TilesError::InvalidTile('\t', Location(1, 0)))]
+    #[case("F-7\n|.|\nL-J", TilesError::MissingStart)]
+    #[case(
+        "S-7\n|.|\nL-S",
+        TilesError::MultipleStarts(Location(0, 0), Location(2, 2))
+    )]
+    #[case(
+        "-S-\n.|.",
+        TilesError::StartConnections(vec![Direction::South, Direction::East, Direction::West])
+    )]
+    #[case("F-S\n|..\nL-J", TilesError::StartConnections(vec![Direction::West]))]
+    #[case(".S.\n...", TilesError::StartConnections(vec![]))]
+    #[case("S-7\n|.|\nL-.", TilesError::BrokenLoop(Location(2, 2)))]
+    #[case("S-7\n|.|\nL-|", TilesError::BrokenLoop(Location(2, 2)))]
+    #[case("S-\n|.\nL-", TilesError::BrokenLoop(Location(2, 2)))]
+    fn test_tiles_parse_error(#[case] input: &str, #[case] expected: TilesError) {
+        assert_eq!(Tiles::parse(input).err(), Some(expected));
+    }
+
+    #[test]
+    fn test_tiles_error_message() {
+        let error = Tiles::parse("S-7\n|.|\nL-.").err().unwrap();
+        assert_eq!(error.to_string(), "loop is broken at row 3, column 3");
+
+        let error = Tiles::parse("S-7\n|x|\nL-J").err().unwrap();
+        assert_eq!(error.to_string(), "invalid tile 'x' at row 2, column 2");
+    }
+
+    #[rstest]
+    #[case(SIMPLE, 4)]
+    #[case(COMPLEX, 8)]
+    fn test_tiles_farthest_distance(#[case] input: &str, #[case] expected: u32) {
+        let tiles = Tiles::parse(input).unwrap();
+        assert_eq!(tiles.farthest_distance(), expected);
+    }
+
+    #[rstest]
+    #[case(SIMPLE, 1)]
+    #[case(COMPLEX, 1)]
+    #[case(ENCLOSED, 4)]
+    #[case(SQUEEZED, 4)]
+    #[case(LARGER, 8)]
+    #[case(JUNK, 10)]
+    #[case("S7\nLJ", 0)]
+    fn test_tiles_enclosed_count(#[case] input: &str, #[case] expected: u32) {
+        let tiles = Tiles::parse(input).unwrap();
+        assert_eq!(tiles.enclosed_count(), expected);
+    }
+
+    #[rstest]
+    #[case(SIMPLE)]
+    #[case(COMPLEX)]
+    #[case(ENCLOSED)]
+    #[case(SQUEEZED)]
+    #[case(LARGER)]
+    #[case(JUNK)]
+    #[case("S7\nLJ")]
+    #[case(include_str!("../input/day_10"))]
+    fn test_tiles_enclosed_count_matches_scanline(#[case] input: &str) {
+        let tiles = Tiles::parse(input).unwrap();
+        assert_eq!(
+            tiles.enclosed_count() as usize,
+            tiles.enclosed_locations().len()
+        );
+    }
+
+    #[test]
+    fn test_tiles_enclosed_locations() {
+        let tiles = Tiles::parse(ENCLOSED).unwrap();
+        assert_eq!(
+            tiles.enclosed_locations(),
+            vec![
+                Location(6, 2),
+                Location(6, 3),
+                Location(6, 7),
+                Location(6, 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tiles_render_plain() {
+        let input = "7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ";
+        let tiles = Tiles::parse(input).unwrap();
+        let result = tiles.render(Format::Plain);
+        let expected = "┐─┏┓─
+ ┏┛┃┐
+┏┛●┗┓
+┃┏━━┛
+┗┛ └┘
+
+16 tiles on the loop, 1 enclosed
+";
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_tiles_render_ansi() {
+        let input = "F7.
+SJ.
+.-7";
+        let tiles = Tiles::parse(input).unwrap();
+        let result = tiles.render(Format::Ansi);
+        let expected = "┌┐ \n\x1b[1;33m└\x1b[0m┘ \n \x1b[2m─\x1b[0m\x1b[2m┐\x1b[0m\n\n4 tiles on the loop, 0 enclosed\n";
+        assert_eq!(result, expected);
+    }
+}
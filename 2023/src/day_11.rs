@@ -0,0 +1,352 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use itertools::Itertools;
+
+pub struct SpaceTime {
+    pub empty_rows: BTreeSet<usize>,
+    pub empty_columns: BTreeSet<usize>,
+    pub galaxies: Vec<Galaxy>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Galaxy(pub usize, pub usize);
+
+impl SpaceTime {
+    fn big_bang(row_count: usize, column_count: usize) -> SpaceTime {
+        SpaceTime {
+            empty_rows: BTreeSet::from_iter(0..row_count),
+            empty_columns: BTreeSet::from_iter(0..column_count),
+            galaxies: Vec::new(),
+        }
+    }
+
+    // Parse the image, recording the galaxies in reading order along with
+    // the rows and columns without any.
+    pub fn parse(input: &str) -> SpaceTime {
+        let row_count = input.lines().count();
+        let column_count = input.lines().next().map_or(0, |line| line.chars().count());
+
+        input
+            .lines()
+            .enumerate()
+            .flat_map(move |(row_index, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter_map(move |(column_index, char)| match char {
+                        '#' => Some(Galaxy(row_index, column_index)),
+                        _ => None,
+                    })
+            })
+            .fold(
+                SpaceTime::big_bang(row_count, column_count),
+                |mut space_time, galaxy| {
+                    space_time.empty_rows.remove(&galaxy.0);
+                    space_time.empty_columns.remove(&galaxy.1);
+                    space_time.galaxies.push(galaxy);
+                    space_time
+                },
+            )
+    }
+
+    // The galaxy coordinates after every empty row is replaced by
+    // `row_factor` rows and every empty column by `column_factor` columns.
+    // A factor of 1 leaves them as they are and 0 removes them.
+    pub fn expand(&self, row_factor: usize, column_factor: usize) -> Vec<Galaxy> {
+        self.galaxies
+            .iter()
+            .map(|galaxy| {
+                let empty_rows = self.empty_rows.range(..galaxy.0).count();
+                let empty_columns = self.empty_columns.range(..galaxy.1).count();
+                Galaxy(
+                    galaxy.0 - empty_rows + empty_rows * row_factor,
+                    galaxy.1 - empty_columns + empty_columns * column_factor,
+                )
+            })
+            .collect()
+    }
+}
+
+impl Galaxy {
+    // Manhattan distance to another galaxy.
+    pub fn distance(&self, other: &Galaxy) -> u64 {
+        (self.0.abs_diff(other.0) + self.1.abs_diff(other.1)) as u64
+    }
+}
+
+// Sum the Manhattan distances between every pair of galaxies by visiting
+// each pair. Quadratic in the number of galaxies.
+pub fn distance_sum_pairwise(galaxies: &[Galaxy]) -> u64 {
+    galaxies
+        .iter()
+        .tuple_combinations()
+        .map(|(a, b)| a.distance(b))
+        .sum()
+}
+
+// Sum the Manhattan distances between every pair of galaxies in
+// O(n log n).
+//
+// The row and column parts of the distance are independent, so each axis is
+// summed separately. With an axis's coordinates sorted, the `i`th is at least
+// as far along as the `i` before it, contributing `i * x_i` minus the sum of
+// those before it.
+pub fn distance_sum(galaxies: &[Galaxy]) -> u64 {
+    axis_distance_sum(galaxies.iter().map(|galaxy| galaxy.0))
+        + axis_distance_sum(galaxies.iter().map(|galaxy| galaxy.1))
+}
+
+fn axis_distance_sum(coordinates: impl Iterator<Item = usize>) -> u64 {
+    let mut prefix_sum = 0;
+    coordinates
+        .map(|coordinate| coordinate as u64)
+        .sorted_unstable()
+        .enumerate()
+        .map(|(index, coordinate)| {
+            let sum = index as u64 * coordinate - prefix_sum;
+            prefix_sum += coordinate;
+            sum
+        })
+        .sum()
+}
+
+// Distance between two galaxies numbered from 1 in reading order, as in the
+// puzzle text. `None` if either doesn't exist.
+pub fn distance_between(galaxies: &[Galaxy], first: usize, second: usize) -> Option<u64> {
+    let first = galaxies.get(first.checked_sub(1)?)?;
+    let second = galaxies.get(second.checked_sub(1)?)?;
+    Some(first.distance(second))
+}
+
+// For each galaxy, the index of the nearest other galaxy and the distance to
+// it, preferring the lowest index on ties. `None` if there are no other
+// galaxies.
+pub fn nearest_galaxies(galaxies: &[Galaxy]) -> Vec<Option<(usize, u64)>> {
+    galaxies
+        .iter()
+        .enumerate()
+        .map(|(index, galaxy)| {
+            galaxies
+                .iter()
+                .enumerate()
+                .filter(|&(other_index, _)| other_index != index)
+                .map(|(other_index, other)| (other_index, galaxy.distance(other)))
+                .min_by_key(|&(other_index, distance)| (distance, other_index))
+        })
+        .collect()
+}
+
+// The indices of the two galaxies furthest apart and the distance between
+// them, in O(n). `None` if there are fewer than two galaxies.
+//
+// Rotating coordinates 45° to `u = row + column` and `v = row - column`
+// turns Manhattan distance into `max(|u₁ - u₂|, |v₁ - v₂|)`, which is
+// largest between the extremes of `u` or `v`.
+pub fn farthest_pair(galaxies: &[Galaxy]) -> Option<(usize, usize, u64)> {
+    if galaxies.len() < 2 {
+        return None;
+    }
+
+    let u = |index: &usize| galaxies[*index].0 as i64 + galaxies[*index].1 as i64;
+    let v = |index: &usize| galaxies[*index].0 as i64 - galaxies[*index].1 as i64;
+    [
+        (0..galaxies.len()).minmax_by_key(u),
+        (0..galaxies.len()).minmax_by_key(v),
+    ]
+    .into_iter()
+    .map(|minmax| {
+        let (first, second) = minmax.into_option().unwrap();
+        let (first, second) = (first.min(second), first.max(second));
+        (first, second, galaxies[first].distance(&galaxies[second]))
+    })
+    .max_by_key(|&(_, _, distance)| distance)
+}
+
+// Export the distance between every pair of galaxies as CSV, with galaxies
+// numbered from 1 in the header row and first column.
+pub fn distance_matrix_csv(galaxies: &[Galaxy]) -> String {
+    let mut output = String::from("galaxy");
+    for number in 1..=galaxies.len() {
+        write!(output, ",{number}").unwrap();
+    }
+    output.push('\n');
+
+    for (index, galaxy) in galaxies.iter().enumerate() {
+        write!(output, "{}", index + 1).unwrap();
+        for other in galaxies {
+            write!(output, ",{}", galaxy.distance(other)).unwrap();
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn test_space_time_parse() {
+        let space_time = SpaceTime::parse(INPUT);
+        assert_eq!(space_time.empty_rows, BTreeSet::from([3, 7]));
+        assert_eq!(space_time.empty_columns, BTreeSet::from([2, 5, 8]));
+        assert_eq!(space_time.galaxies.len(), 9);
+        assert_eq!(space_time.galaxies[0], Galaxy(0, 3));
+        assert_eq!(space_time.galaxies[8], Galaxy(9, 4));
+    }
+
+    #[rstest]
+    #[case(1, 1, Galaxy(9, 4), 292)]
+    #[case(2, 2, Galaxy(11, 5), 374)]
+    #[case(10, 10, Galaxy(27, 13), 1030)]
+    #[case(100, 100, Galaxy(207, 103), 8410)]
+    #[case(2, 1, Galaxy(11, 4), 328)]
+    #[case(1, 2, Galaxy(9, 5), 338)]
+    #[case(0, 0, Galaxy(7, 3), 210)]
+    fn test_space_time_expand(
+        #[case] row_factor: usize,
+        #[case] column_factor: usize,
+        #[case] last: Galaxy,
+        #[case] distance: u64,
+    ) {
+        let galaxies = SpaceTime::parse(INPUT).expand(row_factor, column_factor);
+        assert_eq!(galaxies[8], last);
+        assert_eq!(distance_sum(&galaxies), distance);
+    }
+
+    #[rstest]
+    #[case(vec![], 0)]
+    #[case(vec![Galaxy(3, 4)], 0)]
+    #[case(vec![Galaxy(0, 0), Galaxy(3, 4)], 7)]
+    #[case(vec![Galaxy(3, 4), Galaxy(0, 0), Galaxy(3, 4)], 14)]
+    // The puzzle example after expansion.
+    #[case(
+        vec![
+            Galaxy(0, 4),
+            Galaxy(1, 9),
+            Galaxy(2, 0),
+            Galaxy(5, 8),
+            Galaxy(6, 1),
+            Galaxy(7, 12),
+            Galaxy(10, 9),
+            Galaxy(11, 0),
+            Galaxy(11, 5),
+        ],
+        374
+    )]
+    fn test_distance_sum(#[case] galaxies: Vec<Galaxy>, #[case] expected: u64) {
+        assert_eq!(distance_sum_pairwise(&galaxies), expected);
+        assert_eq!(distance_sum(&galaxies), expected);
+    }
+
+    #[test]
+    fn test_distance_sum_matches_pairwise() {
+        // Scatter galaxies with a linear congruential generator.
+        let mut state: u64 = 11;
+        let galaxies: Vec<Galaxy> = (0..1000)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                Galaxy((state >> 33) as usize % 500, (state >> 13) as usize % 2000)
+            })
+            .collect();
+        assert_eq!(distance_sum(&galaxies), distance_sum_pairwise(&galaxies));
+    }
+
+    #[rstest]
+    #[case(5, 9, Some(9))]
+    #[case(1, 7, Some(15))]
+    #[case(3, 6, Some(17))]
+    #[case(8, 9, Some(5))]
+    #[case(9, 8, Some(5))]
+    #[case(4, 4, Some(0))]
+    #[case(0, 1, None)]
+    #[case(1, 10, None)]
+    fn test_distance_between(
+        #[case] first: usize,
+        #[case] second: usize,
+        #[case] expected: Option<u64>,
+    ) {
+        let galaxies = SpaceTime::parse(INPUT).expand(2, 2);
+        assert_eq!(distance_between(&galaxies, first, second), expected);
+    }
+
+    #[test]
+    fn test_nearest_galaxies() {
+        let galaxies = SpaceTime::parse(INPUT).expand(2, 2);
+        assert_eq!(
+            nearest_galaxies(&galaxies),
+            vec![
+                Some((1, 6)),
+                Some((3, 5)),
+                Some((4, 5)),
+                Some((1, 5)),
+                Some((2, 5)),
+                Some((3, 6)),
+                Some((8, 5)),
+                Some((8, 5)),
+                Some((6, 5)),
+            ]
+        );
+        assert_eq!(nearest_galaxies(&[Galaxy(1, 2)]), vec![None]);
+    }
+
+    #[rstest]
+    #[case(vec![], None)]
+    #[case(vec![Galaxy(1, 2)], None)]
+    #[case(vec![Galaxy(0, 5), Galaxy(5, 0)], Some((0, 1, 10)))]
+    #[case(vec![Galaxy(2, 2), Galaxy(0, 0), Galaxy(1, 3), Galaxy(4, 4)], Some((1, 3, 8)))]
+    #[case(SpaceTime::parse(INPUT).expand(2, 2), Some((1, 7, 19)))]
+    fn test_farthest_pair(
+        #[case] galaxies: Vec<Galaxy>,
+        #[case] expected: Option<(usize, usize, u64)>,
+    ) {
+        assert_eq!(farthest_pair(&galaxies), expected);
+    }
+
+    #[test]
+    fn test_farthest_pair_matches_pairwise() {
+        let mut state: u64 = 7;
+        for galaxy_count in 2..50 {
+            let galaxies: Vec<Galaxy> = (0..galaxy_count)
+                .map(|_| {
+                    state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                    Galaxy((state >> 33) as usize % 40, (state >> 13) as usize % 40)
+                })
+                .collect();
+            let expected = galaxies
+                .iter()
+                .tuple_combinations()
+                .map(|(a, b)| a.distance(b))
+                .max();
+            let (first, second, distance) = farthest_pair(&galaxies).unwrap();
+            assert_eq!(Some(distance), expected);
+            assert_eq!(galaxies[first].distance(&galaxies[second]), distance);
+        }
+    }
+
+    #[test]
+    fn test_distance_matrix_csv() {
+        let galaxies = [Galaxy(0, 4), Galaxy(1, 9), Galaxy(2, 0)];
+        assert_eq!(
+            distance_matrix_csv(&galaxies),
+            "galaxy,1,2,3
+1,0,6,6
+2,6,0,10
+3,6,10,0
+"
+        );
+    }
+}
@@ -0,0 +1,460 @@
+use std::fmt::{self, Display, Formatter};
+
+use itertools::Itertools;
+use winnow::{
+    ascii::{dec_uint, line_ending, space1},
+    combinator::{cut_err, opt, repeat, separated, terminated},
+    error::{ContextError, ParseError, StrContext, StrContextValue},
+    token::one_of,
+    PResult, Parser,
+};
+
+use crate::parsing::trailing_lines;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Condition {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+// A row of spring conditions along with the sizes of each contiguous group of
+// damaged springs in it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Record {
+    pub conditions: Vec<Condition>,
+    pub groups: Vec<usize>,
+}
+
+// Lazily yields every concrete arrangement of a record's conditions, trying
+// operational springs before damaged ones.
+pub struct Arrangements<'a> {
+    record: &'a Record,
+    // Partial arrangements still to be extended: the next position, the next
+    // group and the conditions decided so far.
+    stack: Vec<(usize, usize, Vec<Condition>)>,
+    // Fewest conditions the groups from each index onwards fit in.
+    minimum_lengths: Vec<usize>,
+}
+
+// Parse every line of input into a `Record`.
+pub fn parse_records(input: &str) -> Result<Vec<Record>, ParseError<&str, ContextError>> {
+    terminated(
+        repeat(0.., terminated(Record::parse, opt(line_ending))),
+        trailing_lines,
+    )
+    .parse(input)
+}
+
+impl Condition {
+    fn parse(input: &mut &str) -> PResult<Condition> {
+        one_of(['.', '#', '?'])
+            .map(|char| match char {
+                '.' => Condition::Operational,
+                '#' => Condition::Damaged,
+                _ => Condition::Unknown,
+            })
+            .context(StrContext::Expected(StrContextValue::Description(
+                "`.`, `#` or `?`",
+            )))
+            .parse_next(input)
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let char = match self {
+            Condition::Operational => '.',
+            Condition::Damaged => '#',
+            Condition::Unknown => '?',
+        };
+        write!(f, "{char}")
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.conditions.iter().join(""),
+            self.groups.iter().join(",")
+        )
+    }
+}
+
+impl Record {
+    // Parse an input string slice (eg. "???.### 1,1,3") into `Record`.
+    pub fn parse(input: &mut &str) -> PResult<Record> {
+        let (conditions, (_, groups)) = (
+            repeat(1.., Condition::parse),
+            cut_err((
+                space1.context(StrContext::Expected(' '.into())),
+                separated(
+                    1..,
+                    dec_uint::<_, u32, _>
+                        .verify(|&size| size > 0)
+                        .map(|size| size as usize)
+                        .context(StrContext::Expected(StrContextValue::Description(
+                            "non-zero group size",
+                        ))),
+                    ',',
+                ),
+            )),
+        )
+            .parse_next(input)?;
+
+        Ok(Record { conditions, groups })
+    }
+
+    // Repeat the conditions `factor` times separated by an unknown
+    // condition, and the groups `factor` times.
+    pub fn unfold(&self, factor: usize) -> Record {
+        let conditions = vec![self.conditions.clone(); factor];
+        Record {
+            conditions: conditions.join(&Condition::Unknown),
+            groups: self.groups.repeat(factor),
+        }
+    }
+
+    // Every way the unknown conditions can be filled in to match the groups,
+    // found by a depth-first search independent of `arrangement_count`.
+    pub fn arrangements(&self) -> Arrangements<'_> {
+        let mut minimum_lengths = vec![0; self.groups.len() + 1];
+        for (index, size) in self.groups.iter().enumerate().rev() {
+            // Groups after this one each need a gap before them.
+            minimum_lengths[index] =
+                size + minimum_lengths[index + 1] + (index + 1 < self.groups.len()) as usize;
+        }
+
+        Arrangements {
+            record: self,
+            stack: vec![(0, 0, Vec::with_capacity(self.conditions.len()))],
+            minimum_lengths,
+        }
+    }
+
+    // Count the ways the unknown conditions can be filled in to match the
+    // groups.
+    //
+    // `counts[position][group]` is the number of arrangements of the
+    // conditions from `position` onwards matching the groups from `group`
+    // onwards. Each is found from later positions, either by treating the
+    // condition at `position` as operational, or by starting the next group
+    // there and skipping past it and the operational spring that must follow.
+    pub fn arrangement_count(&self) -> u64 {
+        let conditions = &self.conditions;
+        let groups = &self.groups;
+
+        // Operational springs before each position, to check a group fits
+        // anywhere in constant time.
+        let mut operational_before = vec![0; conditions.len() + 1];
+        for (position, condition) in conditions.iter().enumerate() {
+            operational_before[position + 1] =
+                operational_before[position] + (*condition == Condition::Operational) as usize;
+        }
+
+        let mut counts = vec![vec![0_u64; groups.len() + 1]; conditions.len() + 1];
+        counts[conditions.len()][groups.len()] = 1;
+        for position in (0..conditions.len()).rev() {
+            for group in 0..=groups.len() {
+                let condition = conditions[position];
+                let mut count = 0;
+
+                if condition != Condition::Damaged {
+                    count += counts[position + 1][group];
+                }
+
+                if condition != Condition::Operational && group < groups.len() {
+                    let end = position + groups[group];
+                    if end <= conditions.len()
+                        && operational_before[end] == operational_before[position]
+                        && conditions.get(end) != Some(&Condition::Damaged)
+                    {
+                        count += counts[(end + 1).min(conditions.len())][group + 1];
+                    }
+                }
+
+                counts[position][group] = count;
+            }
+        }
+
+        counts[0][0]
+    }
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = Vec<Condition>;
+
+    fn next(&mut self) -> Option<Vec<Condition>> {
+        let conditions = &self.record.conditions;
+        let groups = &self.record.groups;
+
+        while let Some((position, group, mut arrangement)) = self.stack.pop() {
+            if conditions.len() - position < self.minimum_lengths[group] {
+                continue;
+            }
+            if position == conditions.len() {
+                return Some(arrangement);
+            }
+
+            // The damaged choice is pushed first so the operational one is
+            // tried first.
+            let condition = conditions[position];
+            if condition != Condition::Operational && group < groups.len() {
+                let end = position + groups[group];
+                if end <= conditions.len()
+                    && !conditions[position..end].contains(&Condition::Operational)
+                    && conditions.get(end) != Some(&Condition::Damaged)
+                {
+                    let mut next = arrangement.clone();
+                    next.resize(end, Condition::Damaged);
+                    if end < conditions.len() {
+                        next.push(Condition::Operational);
+                    }
+                    self.stack.push((next.len(), group + 1, next));
+                }
+            }
+
+            if condition != Condition::Damaged {
+                arrangement.push(Condition::Operational);
+                self.stack.push((position + 1, group, arrangement));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_record_parse() {
+        let mut input = "?#. 1,10\n";
+        let result = Record::parse(&mut input).unwrap();
+        assert_eq!(
+            result,
+            Record {
+                conditions: vec![
+                    Condition::Unknown,
+                    Condition::Damaged,
+                    Condition::Operational,
+                ],
+                groups: vec![1, 10],
+            }
+        );
+        assert_eq!(input, "\n");
+    }
+
+    #[test]
+    fn test_parse_records_trailing_blank_lines() {
+        let result = parse_records("?? 1\n#. 1\n\n").unwrap();
+        assert_eq!(result.len(), 2);
+    }
+
+    #[rstest]
+    #[case("???.### 1,1,3\n.x? 1", 15)]
+    #[case("???.### 1,1,", 11)]
+    #[case("???.###", 7)]
+    #[case("???.### a", 8)]
+    #[case("?? 0", 3)]
+    #[case("?? 1\n\n?? 1", 6)]
+    #[case("???.### 1,0,3", 9)]
+    fn test_parse_records_error(#[case] input: &str, #[case] offset: usize) {
+        let error = parse_records(input).unwrap_err();
+        assert_eq!(error.offset(), offset);
+    }
+
+    #[rstest]
+    #[case("???.### 1,1,3", 1)]
+    #[case(".??..??...?##. 1,1,3", 4)]
+    #[case("?#?#?#?#?#?#?#? 1,3,1,6", 1)]
+    #[case("????.#...#... 4,1,1", 1)]
+    #[case("????.######..#####. 1,6,5", 4)]
+    #[case("?###???????? 3,2,1", 10)]
+    #[case("### 3", 1)]
+    #[case("### 2", 0)]
+    #[case("#.# 1", 0)]
+    #[case("??? 4", 0)]
+    #[case("... 1", 0)]
+    #[case("?.? 1", 2)]
+    fn test_record_arrangement_count(#[case] mut input: &str, #[case] expected: u64) {
+        let record = Record::parse(&mut input).unwrap();
+        assert_eq!(record.arrangement_count(), expected);
+    }
+
+    #[rstest]
+    #[case("???.### 1,1,3", 1)]
+    #[case(".??..??...?##. 1,1,3", 16384)]
+    #[case("?#?#?#?#?#?#?#? 1,3,1,6", 1)]
+    #[case("????.#...#... 4,1,1", 16)]
+    #[case("????.######..#####. 1,6,5", 2500)]
+    #[case("?###???????? 3,2,1", 506250)]
+    fn test_record_unfold_arrangement_count(#[case] mut input: &str, #[case] expected: u64) {
+        let record = Record::parse(&mut input).unwrap();
+        assert_eq!(record.unfold(5).arrangement_count(), expected);
+    }
+
+    #[test]
+    fn test_record_unfold() {
+        let mut input = ".# 1";
+        let record = Record::parse(&mut input).unwrap();
+        let mut expected = ".#?.#?.# 1,1,1";
+        assert_eq!(record.unfold(3), Record::parse(&mut expected).unwrap());
+        assert_eq!(record.unfold(1), record);
+        assert_eq!(record.unfold(0).arrangement_count(), 1);
+    }
+
+    // Find arrangements by trying every way of filling in the unknown
+    // conditions.
+    fn arrangements_brute_force(record: &Record) -> Vec<String> {
+        let unknown: Vec<usize> = record
+            .conditions
+            .iter()
+            .positions(|&condition| condition == Condition::Unknown)
+            .collect();
+
+        (0..1_u32 << unknown.len())
+            .map(|mask| {
+                let mut conditions = record.conditions.clone();
+                for (bit, &position) in unknown.iter().enumerate() {
+                    conditions[position] = if mask & (1 << bit) != 0 {
+                        Condition::Damaged
+                    } else {
+                        Condition::Operational
+                    };
+                }
+                conditions
+            })
+            .filter(|conditions| {
+                let groups: Vec<usize> = conditions
+                    .split(|&condition| condition == Condition::Operational)
+                    .map(|group| group.len())
+                    .filter(|&size| size > 0)
+                    .collect();
+                groups == record.groups
+            })
+            .map(|conditions| conditions.iter().join(""))
+            .collect()
+    }
+
+    #[rstest]
+    #[case("???.### 1,1,3", vec!["#.#.###"])]
+    #[case(
+        "?###???????? 3,2,1",
+        vec![
+            ".###....##.#",
+            ".###...##..#",
+            ".###...##.#.",
+            ".###..##...#",
+            ".###..##..#.",
+            ".###..##.#..",
+            ".###.##....#",
+            ".###.##...#.",
+            ".###.##..#..",
+            ".###.##.#...",
+        ]
+    )]
+    #[case("?.# 1", vec!["..#"])]
+    #[case("### 2", vec![])]
+    fn test_record_arrangements(#[case] mut input: &str, #[case] expected: Vec<&str>) {
+        let record = Record::parse(&mut input).unwrap();
+        let result: Vec<String> = record
+            .arrangements()
+            .map(|arrangement| arrangement.iter().join(""))
+            .collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_record_arrangements_lazy() {
+        let mut input = "?.?.?.? 1";
+        let record = Record::parse(&mut input).unwrap().unfold(20);
+        let first = record.arrangements().next().unwrap();
+        assert_eq!(first.len(), 159);
+        assert_eq!(first[0], Condition::Operational);
+    }
+
+    #[test]
+    fn test_record_arrangements_match_brute_force_input() {
+        let input = include_str!("../input/day_12");
+        for record in parse_records(input).unwrap().iter().take(50) {
+            let result: Vec<String> = record
+                .arrangements()
+                .map(|arrangement| arrangement.iter().join(""))
+                .sorted()
+                .collect();
+            let expected: Vec<String> = arrangements_brute_force(record)
+                .into_iter()
+                .sorted()
+                .collect();
+            assert_eq!(result, expected, "{record}");
+        }
+    }
+
+    #[rstest]
+    #[case(".??..??...?##. 1,1,3", 2)]
+    #[case("?###???????? 3,2,1", 2)]
+    #[case("????.#...#... 4,1,1", 2)]
+    #[case("?.?#? 1,1", 3)]
+    #[case("??? 1", 4)]
+    fn test_record_arrangement_count_matches_brute_force(
+        #[case] mut input: &str,
+        #[case] max_factor: usize,
+    ) {
+        let record = Record::parse(&mut input).unwrap();
+        for factor in 1..=max_factor {
+            let record = record.unfold(factor);
+            assert_eq!(
+                record.arrangement_count(),
+                arrangements_brute_force(&record).len() as u64,
+                "{record}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_record_arrangement_count_matches_brute_force_input() {
+        let input = include_str!("../input/day_12");
+        for record in parse_records(input).unwrap().iter().take(50) {
+            assert_eq!(
+                record.arrangement_count(),
+                arrangements_brute_force(record).len() as u64,
+                "{record}"
+            );
+        }
+    }
+
+    #[rstest]
+    #[case(".??..??...?##. 1,1,3", 3)]
+    #[case("?###???????? 3,2,1", 3)]
+    #[case("????.#...#... 4,1,1", 3)]
+    #[case("?#?#?#?#?#?#?#? 1,3,1,6", 5)]
+    #[case("?.?#? 1,1", 5)]
+    #[case("??? 1", 6)]
+    fn test_record_arrangement_count_matches_arrangements(
+        #[case] mut input: &str,
+        #[case] max_factor: usize,
+    ) {
+        let record = Record::parse(&mut input).unwrap();
+        for factor in 1..=max_factor {
+            let record = record.unfold(factor);
+            assert_eq!(
+                record.arrangement_count(),
+                record.arrangements().count() as u64,
+                "{record}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_record_display() {
+        let mut input = "?###???????? 3,2,1";
+        let record = Record::parse(&mut input).unwrap();
+        assert_eq!(record.to_string(), "?###???????? 3,2,1");
+    }
+}
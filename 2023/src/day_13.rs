@@ -0,0 +1,300 @@
+use winnow::{
+    ascii::line_ending,
+    combinator::{repeat, separated, terminated},
+    error::{ContextError, ParseError, StrContext, StrContextValue},
+    token::one_of,
+    PResult, Parser,
+};
+
+use crate::parsing::trailing_lines;
+
+// A pattern of ash (`.`) and rocks (`#`), with each row and column stored as
+// a bitmask of where the rocks are.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Pattern {
+    // Bit `c` of row `r` is set if there's a rock in column `c`.
+    pub rows: Vec<u64>,
+    // Bit `r` of column `c` is set if there's a rock in row `r`.
+    pub columns: Vec<u64>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Reflection {
+    // A vertical line with this many columns to its left.
+    Vertical(usize),
+    // A horizontal line with this many rows above it.
+    Horizontal(usize),
+}
+
+// A cell of a pattern, numbered from 0.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Cell {
+    pub row: usize,
+    pub column: usize,
+}
+
+// Parse the input into patterns separated by blank lines.
+pub fn parse_patterns(input: &str) -> Result<Vec<Pattern>, ParseError<&str, ContextError>> {
+    terminated(
+        separated(1.., Pattern::parse, (line_ending, line_ending)),
+        trailing_lines,
+    )
+    .parse(input)
+}
+
+impl Pattern {
+    // Parse the rows of a single pattern, which must all be the same width
+    // and at most 64 wide and tall so they fit in a bitmask.
+    pub fn parse(input: &mut &str) -> PResult<Pattern> {
+        separated(1.., Pattern::parse_row, line_ending)
+            .verify(|rows: &Vec<Vec<char>>| {
+                rows.len() <= 64
+                    && rows
+                        .iter()
+                        .all(|row| row.len() == rows[0].len() && row.len() <= 64)
+            })
+            .context(StrContext::Expected(StrContextValue::Description(
+                "rows of `.` and `#` of equal width",
+            )))
+            .map(|rows: Vec<Vec<char>>| Pattern::new(&rows))
+            .parse_next(input)
+    }
+
+    fn parse_row(input: &mut &str) -> PResult<Vec<char>> {
+        repeat(1.., one_of(['.', '#'])).parse_next(input)
+    }
+
+    fn new(grid: &[Vec<char>]) -> Pattern {
+        let mut rows = vec![0; grid.len()];
+        let mut columns = vec![0; grid[0].len()];
+        for (row_index, row) in grid.iter().enumerate() {
+            for (column_index, &char) in row.iter().enumerate() {
+                if char == '#' {
+                    rows[row_index] |= 1 << column_index;
+                    columns[column_index] |= 1 << row_index;
+                }
+            }
+        }
+
+        Pattern { rows, columns }
+    }
+
+    // Find every line the pattern reflects across with exactly `smudges`
+    // cells differing from their reflection, vertical lines first.
+    pub fn reflections(&self, smudges: u32) -> Vec<Reflection> {
+        reflections(&self.columns, smudges)
+            .map(Reflection::Vertical)
+            .chain(reflections(&self.rows, smudges).map(Reflection::Horizontal))
+            .collect()
+    }
+
+    // Sum the summaries of every line of reflection with exactly `smudges`
+    // differing cells.
+    pub fn summary(&self, smudges: u32) -> usize {
+        self.reflections(smudges)
+            .iter()
+            .map(Reflection::summary)
+            .sum()
+    }
+
+    // The cells that differ from their reflection across `reflection`, on
+    // the side before the line. Fixing either these or their reflections
+    // makes the reflection perfect.
+    pub fn smudges(&self, reflection: Reflection) -> Vec<Cell> {
+        let (lines, before) = match reflection {
+            Reflection::Vertical(before) => (&self.columns, before),
+            Reflection::Horizontal(before) => (&self.rows, before),
+        };
+
+        let mut cells = vec![];
+        for (index, (a, b)) in lines[..before]
+            .iter()
+            .rev()
+            .zip(&lines[before..])
+            .enumerate()
+        {
+            let line = before - 1 - index;
+            let mut difference = a ^ b;
+            while difference != 0 {
+                let bit = difference.trailing_zeros() as usize;
+                cells.push(match reflection {
+                    Reflection::Vertical(_) => Cell {
+                        row: bit,
+                        column: line,
+                    },
+                    Reflection::Horizontal(_) => Cell {
+                        row: line,
+                        column: bit,
+                    },
+                });
+                difference &= difference - 1;
+            }
+        }
+
+        cells.sort_by_key(|cell| (cell.row, cell.column));
+        cells
+    }
+}
+
+impl Reflection {
+    // Columns left of a vertical line, or 100 times the rows above a
+    // horizontal line.
+    pub fn summary(&self) -> usize {
+        match self {
+            Reflection::Vertical(columns) => *columns,
+            Reflection::Horizontal(rows) => 100 * rows,
+        }
+    }
+}
+
+// Find the lines that `lines` reflect across with exactly `smudges` cells
+// differing, as the number of lines before each. Lines are compared outwards
+// from the line of reflection until either edge is reached, counting the
+// differing cells as the set bits of each pair XORed.
+fn reflections(lines: &[u64], smudges: u32) -> impl Iterator<Item = usize> + '_ {
+    (1..lines.len()).filter(move |&before| {
+        lines[..before]
+            .iter()
+            .rev()
+            .zip(&lines[before..])
+            .try_fold(0, |differences, (a, b)| {
+                Some(differences + (a ^ b).count_ones())
+                    .filter(|&differences| differences <= smudges)
+            })
+            == Some(smudges)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#
+";
+
+    #[test]
+    fn test_parse_patterns_trailing_blank_lines() {
+        let input = format!("{INPUT}\n\n");
+        assert_eq!(
+            parse_patterns(&input).unwrap(),
+            parse_patterns(INPUT).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_patterns() {
+        let result = parse_patterns(INPUT).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].rows.len(), 7);
+        assert_eq!(result[0].columns.len(), 9);
+        assert_eq!(result[0].rows[0], 0b011001101);
+        assert_eq!(result[0].columns[0], 0b1001101);
+    }
+
+    #[rstest]
+    #[case("#.\n#")]
+    #[case("#.\n#.\n\n\n#.")]
+    #[case("#.\n#x")]
+    #[case("")]
+    fn test_parse_patterns_error(#[case] input: &str) {
+        assert!(parse_patterns(input).is_err());
+    }
+
+    #[rstest]
+    #[case(0, vec![Reflection::Vertical(5)], 5)]
+    #[case(1, vec![Reflection::Horizontal(4)], 400)]
+    fn test_pattern_reflections(
+        #[case] index: usize,
+        #[case] expected: Vec<Reflection>,
+        #[case] summary: usize,
+    ) {
+        let pattern = &parse_patterns(INPUT).unwrap()[index];
+        assert_eq!(pattern.reflections(0), expected);
+        assert_eq!(pattern.summary(0), summary);
+    }
+
+    #[rstest]
+    #[case(0, vec![Reflection::Horizontal(3)], 300, vec![Cell { row: 0, column: 0 }])]
+    #[case(1, vec![Reflection::Horizontal(1)], 100, vec![Cell { row: 0, column: 4 }])]
+    fn test_pattern_reflections_smudged(
+        #[case] index: usize,
+        #[case] expected: Vec<Reflection>,
+        #[case] summary: usize,
+        #[case] smudges: Vec<Cell>,
+    ) {
+        let pattern = &parse_patterns(INPUT).unwrap()[index];
+        assert_eq!(pattern.reflections(1), expected);
+        assert_eq!(pattern.summary(1), summary);
+        assert_eq!(pattern.smudges(expected[0]), smudges);
+    }
+
+    #[rstest]
+    #[case("#..\n...", 0, vec![Reflection::Vertical(2)])]
+    #[case("#..\n...", 1, vec![Reflection::Vertical(1), Reflection::Horizontal(1)])]
+    #[case("#..\n...", 2, vec![])]
+    #[case("##.#\n#...", 2, vec![Reflection::Vertical(2), Reflection::Horizontal(1)])]
+    fn test_pattern_reflections_smudge_count(
+        #[case] input: &str,
+        #[case] smudges: u32,
+        #[case] expected: Vec<Reflection>,
+    ) {
+        let pattern = &parse_patterns(input).unwrap()[0];
+        assert_eq!(pattern.reflections(smudges), expected);
+    }
+
+    #[rstest]
+    #[case("##.#\n#...", Reflection::Vertical(2), vec![Cell { row: 0, column: 1 }, Cell { row: 1, column: 0 }])]
+    #[case("#..\n...", Reflection::Horizontal(1), vec![Cell { row: 0, column: 0 }])]
+    #[case("#..\n...", Reflection::Vertical(2), vec![])]
+    fn test_pattern_smudges(
+        #[case] input: &str,
+        #[case] reflection: Reflection,
+        #[case] expected: Vec<Cell>,
+    ) {
+        let pattern = &parse_patterns(input).unwrap()[0];
+        assert_eq!(pattern.smudges(reflection), expected);
+    }
+
+    #[rstest]
+    #[case("#.#", vec![])]
+    #[case("##", vec![Reflection::Vertical(1)])]
+    #[case("#\n#", vec![Reflection::Horizontal(1)])]
+    #[case(
+        "..\n..",
+        vec![Reflection::Vertical(1), Reflection::Horizontal(1)]
+    )]
+    #[case(
+        "#..#\n.##.\n.##.",
+        vec![Reflection::Vertical(2), Reflection::Horizontal(2)]
+    )]
+    fn test_pattern_reflections_edges(#[case] input: &str, #[case] expected: Vec<Reflection>) {
+        let pattern = &parse_patterns(input).unwrap()[0];
+        assert_eq!(pattern.reflections(0), expected);
+    }
+
+    #[test]
+    fn test_pattern_smudges_input() {
+        let input = include_str!("../input/day_13");
+        for pattern in parse_patterns(input).unwrap() {
+            let reflections = pattern.reflections(1);
+            assert_eq!(reflections.len(), 1);
+            assert_eq!(pattern.smudges(reflections[0]).len(), 1);
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+
+use winnow::{
+    ascii::line_ending,
+    combinator::{repeat, separated, terminated},
+    error::{ContextError, ParseError, StrContext, StrContextValue},
+    token::one_of,
+    PResult, Parser,
+};
+
+use crate::parsing::trailing_lines;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Tile {
+    // A rock that rolls when the platform is tilted (`O`).
+    Round,
+    // A rock that stays in place (`#`).
+    Cube,
+    Empty,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Platform {
+    pub grid: Vec<Vec<Tile>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    North,
+    West,
+    South,
+    East,
+}
+
+// Where spinning a platform started repeating: the platform after `start`
+// spin cycles is the same as after `start + period`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Repetition {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Tile {
+    fn parse(input: &mut &str) -> PResult<Tile> {
+        one_of(['O', '#', '.'])
+            .map(|char| match char {
+                'O' => Tile::Round,
+                '#' => Tile::Cube,
+                _ => Tile::Empty,
+            })
+            .parse_next(input)
+    }
+
+    fn char(&self) -> char {
+        match self {
+            Tile::Round => 'O',
+            Tile::Cube => '#',
+            Tile::Empty => '.',
+        }
+    }
+}
+
+impl Platform {
+    // Parse the rows of the platform, which must all be the same width.
+    pub fn parse(input: &str) -> Result<Platform, ParseError<&str, ContextError>> {
+        terminated(
+            separated(1.., Platform::parse_row, line_ending),
+            trailing_lines,
+        )
+        .verify(|grid: &Vec<Vec<Tile>>| grid.iter().all(|row| row.len() == grid[0].len()))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "rows of `O`, `#` and `.` of equal width",
+        )))
+        .map(|grid| Platform { grid })
+        .parse(input)
+    }
+
+    fn parse_row(input: &mut &str) -> PResult<Vec<Tile>> {
+        repeat(1.., Tile::parse).parse_next(input)
+    }
+
+    // Roll every round rock in `direction` until it hits the edge, a cube
+    // rock or another round rock. Each lane (a column when tilting north or
+    // south, a row when tilting west or east) is scanned from the edge the
+    // rocks roll towards, keeping track of the nearest free tile.
+    pub fn tilt(&mut self, direction: Direction) {
+        let (lane_count, lane_length) = match direction {
+            Direction::North | Direction::South => (self.grid[0].len(), self.grid.len()),
+            Direction::West | Direction::East => (self.grid.len(), self.grid[0].len()),
+        };
+
+        for lane in 0..lane_count {
+            let mut free_step = 0;
+            for step in 0..lane_length {
+                let (row, column) = self.lane_location(direction, lane, step);
+                match self.grid[row][column] {
+                    Tile::Cube => free_step = step + 1,
+                    Tile::Round => {
+                        let (free_row, free_column) =
+                            self.lane_location(direction, lane, free_step);
+                        self.grid[row][column] = Tile::Empty;
+                        self.grid[free_row][free_column] = Tile::Round;
+                        free_step += 1;
+                    }
+                    Tile::Empty => (),
+                }
+            }
+        }
+    }
+
+    // The row and column `step` tiles into `lane` from the edge that rocks
+    // roll towards when tilting in `direction`.
+    fn lane_location(&self, direction: Direction, lane: usize, step: usize) -> (usize, usize) {
+        match direction {
+            Direction::North => (step, lane),
+            Direction::South => (self.grid.len() - 1 - step, lane),
+            Direction::West => (lane, step),
+            Direction::East => (lane, self.grid[0].len() - 1 - step),
+        }
+    }
+
+    // Tilt north, west, south and then east.
+    pub fn spin_cycle(&mut self) {
+        for direction in [
+            Direction::North,
+            Direction::West,
+            Direction::South,
+            Direction::East,
+        ] {
+            self.tilt(direction);
+        }
+    }
+
+    // The platform after `count` spin cycles, and where it started repeating
+    // if it did within them.
+    //
+    // Each platform seen is recorded with the number of cycles it took to
+    // reach. Once one is seen again the rest of the cycles just go round the
+    // repetition, so the platform after `count` can be picked from those
+    // already seen.
+    pub fn spin(&self, count: usize) -> (Platform, Option<Repetition>) {
+        let mut seen: HashMap<Platform, usize> = HashMap::new();
+        let mut platforms = vec![self.clone()];
+        let mut platform = self.clone();
+        seen.insert(platform.clone(), 0);
+
+        for cycles in 1..=count {
+            platform.spin_cycle();
+            if let Some(&start) = seen.get(&platform) {
+                let period = cycles - start;
+                let index = start + (count - start) % period;
+                return (
+                    platforms.swap_remove(index),
+                    Some(Repetition { start, period }),
+                );
+            }
+            seen.insert(platform.clone(), cycles);
+            platforms.push(platform.clone());
+        }
+
+        (platform, None)
+    }
+
+    // Each round rock puts a load on the north support beams equal to the
+    // number of rows from it to the south edge, inclusive.
+    pub fn north_load(&self) -> usize {
+        self.grid
+            .iter()
+            .enumerate()
+            .map(|(row_index, row)| {
+                row.iter().filter(|&&tile| tile == Tile::Round).count()
+                    * (self.grid.len() - row_index)
+            })
+            .sum()
+    }
+
+    pub fn render(&self) -> String {
+        self.grid
+            .iter()
+            .map(|row| row.iter().map(Tile::char).chain(['\n']).collect::<String>())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
+
+    #[test]
+    fn test_platform_tilt_north() {
+        let mut platform = Platform::parse(INPUT).unwrap();
+        platform.tilt(Direction::North);
+        let expected = "OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#....
+";
+        assert_eq!(platform.render(), expected);
+        assert_eq!(platform.north_load(), 136);
+    }
+
+    #[rstest]
+    #[case("O\n.\nO\n#\n.\nO", "O\nO\n.\n#\nO\n.", 6 + 5 + 2)]
+    #[case(".O\nO#", "OO\n.#", 4)]
+    #[case("#\nO", "#\nO", 1)]
+    fn test_platform_tilt_north_columns(
+        #[case] input: &str,
+        #[case] expected: &str,
+        #[case] load: usize,
+    ) {
+        let mut platform = Platform::parse(input).unwrap();
+        platform.tilt(Direction::North);
+        assert_eq!(platform, Platform::parse(expected).unwrap());
+        assert_eq!(platform.north_load(), load);
+    }
+
+    #[rstest]
+    #[case(Direction::North, "O.#\n.O.\n#OO", "OO#\n.OO\n#..")]
+    #[case(Direction::South, "O.#\n.O.\n#OO", "..#\nOO.\n#OO")]
+    #[case(Direction::West, "O.#\n.O.\n#OO", "O.#\nO..\n#OO")]
+    #[case(Direction::East, "O.#\n.O.\n#OO", ".O#\n..O\n#OO")]
+    #[case(Direction::East, "OO.O#O.", ".OOO#.O")]
+    #[case(Direction::South, "O\n#\nO\n.\nO", "O\n#\n.\nO\nO")]
+    fn test_platform_tilt(
+        #[case] direction: Direction,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        let mut platform = Platform::parse(input).unwrap();
+        platform.tilt(direction);
+        assert_eq!(platform, Platform::parse(expected).unwrap());
+    }
+
+    #[rstest]
+    #[case(
+        1,
+        ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....
+"
+    )]
+    #[case(
+        3,
+        ".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#...O###.O
+#.OOO#...O
+"
+    )]
+    fn test_platform_spin_cycle(#[case] count: usize, #[case] expected: &str) {
+        let mut platform = Platform::parse(INPUT).unwrap();
+        for _ in 0..count {
+            platform.spin_cycle();
+        }
+        assert_eq!(platform.render(), expected);
+    }
+
+    #[rstest]
+    #[case(0, 104, None)]
+    #[case(3, 69, None)]
+    #[case(10, 69, Some(Repetition { start: 3, period: 7 }))]
+    #[case(1_000_000_000, 64, Some(Repetition { start: 3, period: 7 }))]
+    fn test_platform_spin(
+        #[case] count: usize,
+        #[case] load: usize,
+        #[case] repetition: Option<Repetition>,
+    ) {
+        let platform = Platform::parse(INPUT).unwrap();
+        let (result, result_repetition) = platform.spin(count);
+        assert_eq!(result.north_load(), load);
+        assert_eq!(result_repetition, repetition);
+    }
+
+    #[test]
+    fn test_platform_spin_matches_simulation() {
+        let platform = Platform::parse(INPUT).unwrap();
+        let mut expected = platform.clone();
+        for count in 0..30 {
+            assert_eq!(platform.spin(count).0, expected, "{count}");
+            expected.spin_cycle();
+        }
+    }
+
+    #[test]
+    fn test_platform_parse_trailing_blank_lines() {
+        let input = format!("{INPUT}\n\n");
+        assert_eq!(
+            Platform::parse(&input).unwrap(),
+            Platform::parse(INPUT).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case("O.\n.")]
+    #[case("O.\n\n.#")]
+    #[case("O.\nx.")]
+    #[case("")]
+    fn test_platform_parse_error(#[case] input: &str) {
+        assert!(Platform::parse(input).is_err());
+    }
+}
@@ -0,0 +1,12 @@
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_06;
+pub mod day_09;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+
+mod parsing;
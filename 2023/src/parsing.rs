@@ -0,0 +1,26 @@
+use winnow::{ascii::line_ending, combinator::repeat, PResult, Parser};
+
+// Any number of line endings (including none), so input may end with blank
+// lines after its last line.
+pub(crate) fn trailing_lines(input: &mut &str) -> PResult<()> {
+    repeat(0.., line_ending).parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("", "")]
+    #[case("\n", "")]
+    #[case("\n\n\n", "")]
+    #[case("\r\n\r\n", "")]
+    #[case("\n\nx", "x")]
+    #[case("x\n", "x\n")]
+    fn test_trailing_lines(#[case] mut input: &str, #[case] rest: &str) {
+        trailing_lines(&mut input).unwrap();
+        assert_eq!(input, rest);
+    }
+}